@@ -0,0 +1,49 @@
+//! Compares asking a game for the moves of every tile with and without the move cache
+//!
+//! Run with `cargo run --release --example move_cache`
+
+use std::time::Instant;
+
+use fritiofr_chess::{Game, MoveCache};
+
+const ROUNDS: u32 = 10_000;
+
+fn main() {
+    let games = [
+        Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -").unwrap(),
+        Game::start_pos(),
+    ];
+
+    let start = Instant::now();
+    let mut uncached_moves = 0;
+    for round in 0..ROUNDS {
+        let game = games[round as usize % 2];
+        for y in 0..8 {
+            for x in 0..8 {
+                uncached_moves += game.gen_moves(x, y).map_or(0, |m| m.len());
+            }
+        }
+    }
+    let uncached = start.elapsed();
+
+    let start = Instant::now();
+    let mut cache = MoveCache::new();
+    let mut cached_moves = 0;
+    for round in 0..ROUNDS {
+        // Switching games every round makes the moves be generated once per round, like a UI
+        // where a move is made between asking about the tiles
+        let game = games[round as usize % 2];
+        for y in 0..8 {
+            for x in 0..8 {
+                cached_moves += cache.legal_moves_from(&game, x, y).len();
+            }
+        }
+    }
+    let cached = start.elapsed();
+
+    assert_eq!(uncached_moves, cached_moves);
+
+    println!("{} rounds of asking for the moves of all 64 tiles", ROUNDS);
+    println!("gen_moves:                   {:?}", uncached);
+    println!("MoveCache::legal_moves_from: {:?}", cached);
+}
@@ -0,0 +1,67 @@
+//! Measures the speed of the move generator
+//!
+//! Run with `cargo run --release --example perft`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use fritiofr_chess::Game;
+
+/// Counts the heap allocations, to see how much the move generator allocates
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let positions = [
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -", 4),
+        (
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+            3,
+        ),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - -", 5),
+    ];
+
+    let mut total_nodes = 0;
+    let start = Instant::now();
+
+    for (fen, depth) in positions {
+        let game = Game::from_fen(fen).unwrap();
+
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let position_start = Instant::now();
+        let nodes = game.perft(depth);
+        let elapsed = position_start.elapsed();
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+        println!(
+            "{fen} depth {depth}: {nodes} nodes in {:.2?} ({:.0} nps, {allocations} allocations)",
+            elapsed,
+            nodes as f64 / elapsed.as_secs_f64()
+        );
+
+        total_nodes += nodes;
+    }
+
+    let elapsed = start.elapsed();
+    println!(
+        "Total: {total_nodes} nodes in {:.2?} ({:.0} nps)",
+        elapsed,
+        total_nodes as f64 / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,169 @@
+/// A set of tiles stored as one bit per tile
+///
+/// Bit `y * 8 + x` is the tile `(x, y)`, the same order as the tiles of a [`crate::Board`], so
+/// bit 0 is a1 and bit 63 is h8.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::Bitboard;
+///
+/// let mut bitboard = Bitboard::default();
+/// bitboard.set(4, 7);
+/// bitboard.set(0, 0);
+///
+/// assert!(bitboard.is_set(4, 7));
+/// assert_eq!(bitboard.squares().collect::<Vec<_>>(), vec![(0, 0), (4, 7)]);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    /// Adds a tile to the set
+    ///
+    /// # Arguments
+    /// * `x` - The file of the tile, 0 for the a file
+    /// * `y` - The rank of the tile, 0 for the 1st rank
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.0 |= bit(x, y);
+    }
+
+    /// Removes a tile from the set
+    ///
+    /// # Arguments
+    /// * `x` - The file of the tile, 0 for the a file
+    /// * `y` - The rank of the tile, 0 for the 1st rank
+    pub fn clear(&mut self, x: usize, y: usize) {
+        self.0 &= !bit(x, y);
+    }
+
+    /// Returns if a tile is in the set
+    ///
+    /// # Arguments
+    /// * `x` - The file of the tile, 0 for the a file
+    /// * `y` - The rank of the tile, 0 for the 1st rank
+    pub fn is_set(&self, x: usize, y: usize) -> bool {
+        self.0 & bit(x, y) != 0
+    }
+
+    /// Returns the amount of tiles in the set
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Returns if there are no tiles in the set
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Removes the tile with the lowest index from the set and returns it
+    ///
+    /// # Returns
+    /// * `Option<(usize, usize)>` - The removed tile or None if the set is empty
+    pub fn pop_lsb(&mut self) -> Option<(usize, usize)> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let tile = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+
+        Some((tile % 8, tile / 8))
+    }
+
+    /// Returns an iterator over the tiles in the set, from a1 to h8
+    pub fn squares(&self) -> BitboardIter {
+        BitboardIter(*self)
+    }
+}
+
+impl From<u64> for Bitboard {
+    fn from(bits: u64) -> Self {
+        Bitboard(bits)
+    }
+}
+
+impl IntoIterator for Bitboard {
+    type Item = (usize, usize);
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.squares()
+    }
+}
+
+/// An iterator over the tiles of a [`Bitboard`], see [`Bitboard::squares`]
+#[derive(Clone, Debug)]
+pub struct BitboardIter(Bitboard);
+
+impl Iterator for BitboardIter {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_lsb()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for BitboardIter {}
+
+/// Internal helper that returns the bit of a tile
+fn bit(x: usize, y: usize) -> u64 {
+    debug_assert!(x < 8 && y < 8, "x and y must be between 0 and 7");
+
+    1 << (y * 8 + x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    pub fn squares_in_index_order() {
+        let tiles = [(7, 7), (0, 0), (3, 4), (7, 0), (0, 7), (5, 2)];
+
+        let mut bitboard = Bitboard::default();
+        for (x, y) in tiles {
+            bitboard.set(x, y);
+        }
+
+        assert_eq!(bitboard.count(), 6);
+        assert_eq!(bitboard.squares().len(), 6);
+        assert_eq!(
+            bitboard.squares().collect::<Vec<_>>(),
+            vec![(0, 0), (7, 0), (5, 2), (3, 4), (0, 7), (7, 7)]
+        );
+
+        for (x, y) in tiles {
+            assert!(bitboard.is_set(x, y));
+        }
+        assert!(!bitboard.is_set(1, 0));
+    }
+
+    #[test]
+    pub fn set_clear_and_pop() {
+        let mut bitboard = Bitboard::from(0);
+        assert!(bitboard.is_empty());
+        assert_eq!(bitboard.pop_lsb(), None);
+
+        bitboard.set(2, 3);
+        bitboard.set(2, 3);
+        bitboard.set(6, 1);
+        assert_eq!(bitboard, Bitboard(1 << 26 | 1 << 14));
+
+        bitboard.clear(2, 3);
+        bitboard.clear(0, 0);
+        assert_eq!(bitboard.into_iter().collect::<Vec<_>>(), vec![(6, 1)]);
+
+        assert_eq!(bitboard.pop_lsb(), Some((6, 1)));
+        assert!(bitboard.is_empty());
+
+        let all = Bitboard(!0);
+        assert_eq!(all.squares().count(), 64);
+        assert_eq!(all.squares().last(), Some((7, 7)));
+    }
+}
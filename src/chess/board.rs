@@ -1,12 +1,40 @@
-use crate::{error::FromFenError, Color, Piece, PieceType};
+use alloc::{string::String, string::ToString, vec::Vec};
+
+use crate::{coord, error::FromFenError, Bitboard, Color, Piece, PieceType};
 
 /// A chess board
+///
+/// The pieces are stored both as a list of tiles and as bitboards, one `u64` for each piece type
+/// and color where bit `y * 8 + x` is set if there is a piece on that tile, so bit 0 is a1 and bit
+/// 63 is h8. The tiles are used
+/// for looking up a single tile, the bitboards for finding pieces fast.
 #[derive(Debug, Copy, Clone)]
 pub struct Board {
     pub(crate) tiles: [Option<Piece>; 64],
+    /// The tiles of each piece type, indexed by `PieceType as usize`
+    pieces: [u64; 6],
+    /// The tiles of each color, indexed by `Color as usize`
+    colors: [u64; 2],
 }
 
 impl Board {
+    /// Internal helper that creates a board from a list of tiles and builds the bitboards
+    fn from_tiles(tiles: [Option<Piece>; 64]) -> Board {
+        let mut board = Board {
+            tiles: [None; 64],
+            pieces: [0; 6],
+            colors: [0; 2],
+        };
+
+        for (i, tile) in tiles.into_iter().enumerate() {
+            if let Some(piece) = tile {
+                board.set_tile(i % 8, i / 8, piece);
+            }
+        }
+
+        board
+    }
+
     /// Parses the board part of a FEN string
     ///
     /// # Arguments
@@ -25,54 +53,247 @@ impl Board {
         let rows = fen.split('/').collect::<Vec<&str>>();
 
         if rows.len() != 8 {
-            return Err(FromFenError::IncorrectAmountOfSlash);
+            return Err(FromFenError::WrongRankCount);
         }
 
-        let mut i = 0;
         for (row_index, row) in rows.iter().enumerate() {
-            for c in row.chars() {
-                let parsed_value = c.to_string().parse::<usize>();
+            // The first row of a FEN string is rank 8, which is y = 7
+            let rank = 8 - row_index;
+            let mut file = 0;
 
-                if i >= row_index * 8 + 8 {
-                    return Err(FromFenError::IncorrectAmountOfTiles);
+            for c in row.chars() {
+                if file >= 8 {
+                    return Err(FromFenError::RankOverflow { rank });
                 }
 
-                if let Ok(n) = parsed_value {
-                    i += n;
+                if let Some(n) = c.to_digit(10) {
+                    if n == 0 {
+                        return Err(FromFenError::UnknownCharacter);
+                    }
+
+                    file += n as usize;
                 } else {
                     let piece = Piece::try_from(c).map_err(|_| FromFenError::UnknownCharacter)?;
 
-                    tiles[i] = Some(piece);
+                    tiles[(rank - 1) * 8 + file] = Some(piece);
 
-                    i += 1;
+                    file += 1;
                 }
             }
+
+            if file > 8 {
+                return Err(FromFenError::RankOverflow { rank });
+            }
+            if file < 8 {
+                return Err(FromFenError::RankUnderflow { rank });
+            }
+        }
+
+        Ok(Board::from_tiles(tiles))
+    }
+
+    /// Returns the board as a two dimensional array
+    ///
+    /// The array is indexed as `array[y][x]`, where row 0 is rank 1 and file 0 is the a file,
+    /// the same as the `(x, y)` coordinates used everywhere else. A FEN string lists the rows in
+    /// the opposite order, rank 8 first.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color, Piece, PieceType};
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+    /// let array = board.to_array();
+    ///
+    /// // The black king on e8
+    /// assert_eq!(
+    ///     array[7][4],
+    ///     Some(Piece { piece_type: PieceType::King, color: Color::Black })
+    /// );
+    /// ```
+    pub fn to_array(&self) -> [[Option<Piece>; 8]; 8] {
+        let mut array = [[None; 8]; 8];
+
+        for (y, row) in array.iter_mut().enumerate() {
+            row.copy_from_slice(&self.tiles[y * 8..y * 8 + 8]);
         }
 
-        if i != 64 {
-            return Err(FromFenError::IncorrectAmountOfTiles);
+        array
+    }
+
+    /// Returns the board as a two dimensional array of FEN letters
+    ///
+    /// The array uses the same orientation as [`Board::to_array`], indexed as `array[y][x]` where
+    /// row 0 is rank 1, so a front-end draws the rows in reverse to show white at the bottom.
+    /// White pieces are `PNBRQK`, black pieces `pnbrqk` and empty tiles a space.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Board;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3").unwrap();
+    /// let grid = board.to_char_grid();
+    ///
+    /// assert_eq!(grid[7][4], 'k');
+    /// assert_eq!(grid[0][4], 'K');
+    /// assert_eq!(grid[4][4], ' ');
+    /// ```
+    pub fn to_char_grid(&self) -> [[char; 8]; 8] {
+        self.to_array()
+            .map(|row| row.map(|tile| tile.map(char::from).unwrap_or(' ')))
+    }
+
+    /// Creates a board from a two dimensional array
+    ///
+    /// The array uses the same orientation as [`Board::to_array`], row 0 is rank 1.
+    ///
+    /// # Arguments
+    /// * `array` - The pieces indexed as `array[y][x]`
+    pub fn from_array(array: [[Option<Piece>; 8]; 8]) -> Board {
+        let mut tiles = [None; 64];
+
+        for (y, row) in array.iter().enumerate() {
+            tiles[y * 8..y * 8 + 8].copy_from_slice(row);
+        }
+
+        Board::from_tiles(tiles)
+    }
+
+    /// Returns the board flipped upside down with the colors of all pieces swapped
+    ///
+    /// The position is the same from the other side's point of view, so an evaluation should
+    /// give the mirrored board the negated score.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Board;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3").unwrap();
+    /// assert_eq!(board.mirror_vertical().fen(), "4k3/4p3/8/8/8/8/8/4K3");
+    /// ```
+    pub fn mirror_vertical(&self) -> Board {
+        let mut tiles = [None; 64];
+
+        for (i, tile) in self.tiles.iter().enumerate() {
+            let (x, y) = (i % 8, i / 8);
+
+            tiles[(7 - y) * 8 + x] = tile.map(|piece| Piece {
+                piece_type: piece.piece_type,
+                color: piece.color.opposite(),
+            });
         }
 
-        Ok(Board { tiles })
+        Board::from_tiles(tiles)
+    }
+
+    /// Returns the board flipped so the a file becomes the h file
+    pub fn mirror_horizontal(&self) -> Board {
+        let mut tiles = [None; 64];
+
+        for (i, tile) in self.tiles.iter().enumerate() {
+            let (x, y) = (i % 8, i / 8);
+
+            tiles[y * 8 + 7 - x] = *tile;
+        }
+
+        Board::from_tiles(tiles)
+    }
+
+    /// Returns the board turned half a turn, so a8 becomes h1, without swapping any colors
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Board;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/P7/4K3").unwrap();
+    /// assert_eq!(board.rotate_180().fen(), "3K4/7P/8/8/8/8/8/3k4");
+    /// ```
+    pub fn rotate_180(&self) -> Board {
+        let mut tiles = self.tiles;
+        tiles.reverse();
+
+        Board::from_tiles(tiles)
     }
 
     /// Returns the position of the king of a color
     pub fn get_king_pos(&self, color: Color) -> Option<(usize, usize)> {
-        (0..8)
-            .map(|x| (0..8).map(move |y| (x, y)))
-            .flatten()
-            .find(|(x, y)| {
-                if let Some(piece) = self.get_tile(*x, *y) {
-                    return piece.color == color && piece.piece_type == PieceType::King;
-                }
-                false
-            })
+        let kings = self.pieces(PieceType::King, color);
+
+        if kings == 0 {
+            return None;
+        }
+
+        let i = kings.trailing_zeros() as usize;
+
+        Some((i % 8, i / 8))
+    }
+
+    /// Returns how many pieces of a type and color are on the board
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color, PieceType};
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+    /// assert_eq!(board.piece_count(Color::White, PieceType::Pawn), 8);
+    /// ```
+    pub fn piece_count(&self, color: Color, piece_type: PieceType) -> u8 {
+        self.pieces(piece_type, color).count_ones() as u8
+    }
+
+    /// Returns how many pieces of every type and color are on the board
+    ///
+    /// # Returns
+    /// * `HashMap<(Color, PieceType), u8>` - The count of every piece type and color, pieces that
+    ///   aren't on the board are counted as 0
+    #[cfg(feature = "std")]
+    pub fn piece_counts(&self) -> std::collections::HashMap<(Color, PieceType), u8> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .flat_map(|color| PieceType::all().map(|piece_type| (color, piece_type)))
+            .map(|(color, piece_type)| ((color, piece_type), self.piece_count(color, piece_type)))
+            .collect()
+    }
+
+    /// Internal helper that returns the bitboard of a piece type and color
+    pub(crate) fn pieces(&self, piece_type: PieceType, color: Color) -> u64 {
+        self.pieces[piece_type as usize] & self.colors[color as usize]
+    }
+
+    /// Internal helper that returns the bitboard of all pieces of a color
+    pub(crate) fn color_pieces(&self, color: Color) -> u64 {
+        self.colors[color as usize]
+    }
+
+    /// Internal helper that returns the bitboard of all tiles with a piece on them
+    pub(crate) fn occupied(&self) -> u64 {
+        self.colors[0] | self.colors[1]
     }
 
     /// Returns a piece on the board
+    ///
+    /// # Arguments
+    /// * `x` - The file of the tile, 0 for the a file
+    /// * `y` - The rank of the tile, 0 for the 1st rank
+    ///
+    /// # Returns
+    /// * `Option<Piece>` - The piece on the tile or None if the tile is empty or `x` or `y` is
+    ///   outside `0..8`
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color, Piece, PieceType};
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+    /// assert_eq!(
+    ///     board.get_tile(4, 0),
+    ///     Some(Piece { piece_type: PieceType::King, color: Color::White })
+    /// );
+    /// assert_eq!(board.get_tile(8, 0), None);
+    /// ```
     pub fn get_tile(&self, x: usize, y: usize) -> Option<Piece> {
         if x > 7 || y > 7 {
-            panic!("x and y must be between 0 and 7");
+            return None;
         }
 
         let index = y * 8 + x;
@@ -80,15 +301,98 @@ impl Board {
         self.tiles[index]
     }
 
+    /// Returns if there is no piece on a tile
+    ///
+    /// Tiles outside the board are empty, the same as for [`Board::get_tile`].
+    ///
+    /// # Arguments
+    /// * `x` - The file of the tile, 0 for the a file
+    /// * `y` - The rank of the tile, 0 for the 1st rank
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Board;
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+    /// assert!(board.is_empty(4, 3));
+    /// assert!(!board.is_empty(4, 1));
+    /// ```
+    pub fn is_empty(&self, x: usize, y: usize) -> bool {
+        self.get_tile(x, y).is_none()
+    }
+
+    /// Returns if a tile has a piece of a color on it
+    ///
+    /// # Arguments
+    /// * `x` - The file of the tile, 0 for the a file
+    /// * `y` - The rank of the tile, 0 for the 1st rank
+    /// * `color` - The color of the piece
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color};
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+    /// assert!(board.is_occupied_by(4, 1, Color::White));
+    /// assert!(!board.is_occupied_by(4, 1, Color::Black));
+    /// ```
+    pub fn is_occupied_by(&self, x: usize, y: usize, color: Color) -> bool {
+        self.get_tile(x, y)
+            .is_some_and(|piece| piece.color == color)
+    }
+
+    /// Returns the tiles with a piece of a color on them
+    ///
+    /// # Arguments
+    /// * `color` - The color of the pieces
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color};
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3").unwrap();
+    /// let tiles: Vec<_> = board.occupancy(Color::White).squares().collect();
+    ///
+    /// assert_eq!(tiles, vec![(0, 0), (4, 0)]);
+    /// ```
+    pub fn occupancy(&self, color: Color) -> Bitboard {
+        Bitboard(self.color_pieces(color))
+    }
+
+    /// Returns a piece on the board from an algebraic tile
+    ///
+    /// # Arguments
+    /// * `tile` - The tile, e.g `e4`
+    ///
+    /// # Returns
+    /// * `Option<Piece>` - The piece on the tile or None if the tile is empty or isn't a valid
+    ///   tile
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color, Piece, PieceType};
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+    /// assert_eq!(
+    ///     board.at("e1"),
+    ///     Some(Piece { piece_type: PieceType::King, color: Color::White })
+    /// );
+    /// assert_eq!(board.at("e4"), None);
+    /// ```
+    pub fn at(&self, tile: &str) -> Option<Piece> {
+        let (x, y) = coord::from_algebraic(tile)?;
+        self.get_tile(x, y)
+    }
+
     /// Sets a tile on the board
     pub fn set_tile(&mut self, x: usize, y: usize, piece: Piece) {
-        if x > 7 || y > 7 {
-            panic!("x and y must be between 0 and 7");
-        }
+        self.remove_tile(x, y);
 
         let index = y * 8 + x;
 
         self.tiles[index] = Some(piece);
+        self.pieces[piece.piece_type as usize] |= 1 << index;
+        self.colors[piece.color as usize] |= 1 << index;
     }
 
     /// Removes a tile from the board
@@ -99,41 +403,41 @@ impl Board {
 
         let index = y * 8 + x;
 
-        self.tiles[index] = None;
+        if let Some(piece) = self.tiles[index].take() {
+            self.pieces[piece.piece_type as usize] &= !(1 << index);
+            self.colors[piece.color as usize] &= !(1 << index);
+        }
     }
 
     /// Returns the board as a FEN string
     pub fn fen(&self) -> String {
         let mut fen = String::new();
 
-        let mut empty_tiles = 0;
+        // A FEN string starts with rank 8
+        for y in (0..8).rev() {
+            let mut empty_tiles = 0;
 
-        for (i, tile) in self.tiles.into_iter().enumerate() {
-            if i % 8 == 0 && i != 0 {
-                if empty_tiles != 0 {
-                    fen.push_str(&empty_tiles.to_string());
-                    empty_tiles = 0;
-                }
+            for tile in &self.tiles[y * 8..y * 8 + 8] {
+                if let Some(piece) = tile {
+                    if empty_tiles != 0 {
+                        fen.push_str(&empty_tiles.to_string());
+                        empty_tiles = 0;
+                    }
 
-                fen.push('/');
-            }
+                    let piece_char: char = (*piece).into();
 
-            if let Some(piece) = tile {
-                if empty_tiles != 0 {
-                    fen.push_str(&empty_tiles.to_string());
-                    empty_tiles = 0;
+                    fen.push(piece_char);
+                } else {
+                    empty_tiles += 1;
                 }
-
-                let piece_char: char = piece.into();
-
-                fen.push(piece_char);
-            } else {
-                empty_tiles += 1;
             }
-        }
 
-        if empty_tiles != 0 {
-            fen.push_str(&empty_tiles.to_string());
+            if empty_tiles != 0 {
+                fen.push_str(&empty_tiles.to_string());
+            }
+            if y != 0 {
+                fen.push('/');
+            }
         }
 
         fen
@@ -143,28 +447,47 @@ impl Board {
 impl Eq for Board {}
 impl PartialEq for Board {
     fn eq(&self, other: &Self) -> bool {
-        self.tiles
-            .into_iter()
-            .zip(other.tiles.into_iter())
-            .all(|(a, b)| a == b)
+        self.tiles.into_iter().zip(other.tiles).all(|(a, b)| a == b)
     }
 }
 
-impl std::fmt::Display for Board {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// Indexes the board with `(x, y)`, the same as [`Board::get_tile`]
+///
+/// There is no `IndexMut`, since the bitboards have to be updated when a tile changes, use
+/// [`Board::set_tile`] and [`Board::remove_tile`] instead.
+///
+/// # Panics
+/// If x or y is greater than 7
+impl core::ops::Index<(usize, usize)> for Board {
+    type Output = Option<Piece>;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        if x > 7 || y > 7 {
+            panic!("x and y must be between 0 and 7");
+        }
+
+        &self.tiles[y * 8 + x]
+    }
+}
+
+impl core::fmt::Display for Board {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mut game_string = String::new();
 
-        for (i, tile) in self.tiles.iter().enumerate() {
-            if i % 8 == 0 && i != 0 {
-                game_string.push_str("\n");
-            }
+        // Rank 8 is printed first, like a board seen from white's side
+        for y in (0..8).rev() {
+            for tile in &self.tiles[y * 8..y * 8 + 8] {
+                if let Some(piece) = tile {
+                    let piece_char: char = (*piece).into();
 
-            if let Some(piece) = tile {
-                let piece_char: char = (*piece).into();
+                    game_string.push(piece_char);
+                } else {
+                    game_string.push('-');
+                }
+            }
 
-                game_string.push(piece_char);
-            } else {
-                game_string.push('-');
+            if y != 0 {
+                game_string.push('\n');
             }
         }
 
@@ -189,4 +512,262 @@ mod tests {
             assert_eq!(board.fen(), fen);
         }
     }
+
+    #[test]
+    pub fn from_fen_rejects_wrong_rank_and_file_counts() {
+        assert!(matches!(
+            Board::from_fen("8/8/8/8/8/8/8"),
+            Err(FromFenError::WrongRankCount)
+        ));
+        assert!(matches!(
+            Board::from_fen("8/8/8/8/8/8/8/8/8"),
+            Err(FromFenError::WrongRankCount)
+        ));
+        assert!(matches!(
+            Board::from_fen(""),
+            Err(FromFenError::WrongRankCount)
+        ));
+        assert!(matches!(
+            Board::from_fen("9/8/8/8/8/8/8/8"),
+            Err(FromFenError::RankOverflow { rank: 8 })
+        ));
+        assert!(matches!(
+            Board::from_fen("pppppppp9/8/8/8/8/8/8/8"),
+            Err(FromFenError::RankOverflow { rank: 8 })
+        ));
+        assert!(matches!(
+            Board::from_fen("8/8/8/8/8/8/PPPPPPPPP/8"),
+            Err(FromFenError::RankOverflow { rank: 2 })
+        ));
+        assert!(matches!(
+            Board::from_fen("8/8/8/8/3k5/8/8/4K3"),
+            Err(FromFenError::RankOverflow { rank: 4 })
+        ));
+        assert!(matches!(
+            Board::from_fen("8/8/8/4k3/8/8/8/4K2"),
+            Err(FromFenError::RankUnderflow { rank: 1 })
+        ));
+        assert!(matches!(
+            Board::from_fen("8/8//8/8/8/8/8"),
+            Err(FromFenError::RankUnderflow { rank: 6 })
+        ));
+        assert!(matches!(
+            Board::from_fen("8/8/8/8/8/8/8/08"),
+            Err(FromFenError::UnknownCharacter)
+        ));
+
+        #[cfg(feature = "std")]
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/7").unwrap_err().to_string(),
+            "Rank 1 of the FEN board has less than 8 files"
+        );
+    }
+
+    #[test]
+    pub fn to_array_should_be_same_as_from_array() {
+        let board =
+            Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1").unwrap();
+        let array = board.to_array();
+
+        assert_eq!(Board::from_array(array), board);
+        assert_eq!(
+            array[0][6],
+            Some(Piece {
+                piece_type: PieceType::King,
+                color: Color::White
+            })
+        );
+        assert_eq!(array[5][0], None);
+    }
+
+    #[test]
+    pub fn char_grid_of_the_start_position() {
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+        let rows = board
+            .to_char_grid()
+            .map(|row| row.iter().collect::<String>());
+
+        assert_eq!(
+            rows.join("|"),
+            "RNBQKBNR|PPPPPPPP|        |        |        |        |pppppppp|rnbqkbnr"
+        );
+    }
+
+    #[test]
+    pub fn bitboards_follow_the_tiles() {
+        let mut board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+
+        assert_eq!(board.occupied(), 0xFFFF_0000_0000_FFFF);
+        assert_eq!(
+            board.pieces(PieceType::Pawn, Color::White),
+            0x0000_0000_0000_FF00
+        );
+        assert_eq!(board.get_king_pos(Color::Black), Some((4, 7)));
+
+        let queen = Piece {
+            piece_type: PieceType::Queen,
+            color: Color::Black,
+        };
+        // Replacing the white king removes it from the white bitboards
+        board.set_tile(4, 0, queen);
+        assert_eq!(board.get_king_pos(Color::White), None);
+        assert_eq!(
+            board.pieces(PieceType::Queen, Color::Black),
+            1 << 59 | 1 << 4
+        );
+
+        board.remove_tile(4, 0);
+        assert_eq!(board.pieces(PieceType::Queen, Color::Black), 1 << 59);
+        assert_eq!(board.occupied().count_ones(), 31);
+    }
+
+    #[test]
+    pub fn mirroring_twice_gives_the_same_board() {
+        let start = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+
+        assert_eq!(start.mirror_vertical(), start);
+        assert_eq!(start.mirror_horizontal().mirror_horizontal(), start);
+
+        let board =
+            Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1").unwrap();
+        assert_eq!(board.mirror_vertical().mirror_vertical(), board);
+        assert_eq!(
+            board.mirror_horizontal().fen(),
+            "r2k3r/ppp1pppP/Nbn3b1/6Pn/3P1PBB/2N4q/PP2P1pP/1KR1Q2R"
+        );
+    }
+
+    #[test]
+    pub fn rotating_keeps_the_colors() {
+        let start = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+        let rotated = start.rotate_180();
+
+        assert_eq!(rotated.fen(), "RNBKQBNR/PPPPPPPP/8/8/8/8/pppppppp/rnbkqbnr");
+
+        // The white rook on h1 is now on a8, where the black rook was, but is still white
+        assert_eq!(
+            rotated.get_tile(0, 7),
+            Some(Piece {
+                piece_type: PieceType::Rook,
+                color: Color::White
+            })
+        );
+        assert_eq!(
+            rotated.pieces(PieceType::Rook, Color::White),
+            1 << 56 | 1 << 63
+        );
+        assert_eq!(rotated.rotate_180(), start);
+
+        let board =
+            Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1").unwrap();
+        let rotated = board.rotate_180();
+        for y in 0..8 {
+            for x in 0..8 {
+                assert_eq!(rotated.get_tile(7 - x, 7 - y), board.get_tile(x, y));
+            }
+        }
+        assert_eq!(rotated.occupied(), board.occupied().reverse_bits());
+    }
+
+    #[test]
+    pub fn coordinates_follow_the_square_names() {
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+
+        for x in 0..8 {
+            for y in 0..8 {
+                assert_eq!(board.get_tile(x, y), board.at(&coord::to_algebraic((x, y))));
+            }
+        }
+
+        // Row 0 is rank 1, the last row of the FEN
+        assert_eq!(coord::from_algebraic("a1"), Some((0, 0)));
+        assert_eq!(coord::from_algebraic("h8"), Some((7, 7)));
+        assert_eq!(coord::from_algebraic("e1"), Some((4, 0)));
+        assert_eq!(board.get_tile(4, 0).unwrap().color, Color::White);
+        assert_eq!(board.get_tile(4, 0).unwrap().piece_type, PieceType::King);
+        assert_eq!(board.get_tile(3, 7).unwrap().color, Color::Black);
+        assert_eq!(board.get_tile(3, 7).unwrap().piece_type, PieceType::Queen);
+        assert_eq!(board.to_char_grid()[1], ['P'; 8]);
+        assert_eq!(board.to_string().lines().next(), Some("rnbqkbnr"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn piece_counts_of_the_start_position_and_an_endgame() {
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+        let counts = board.piece_counts();
+
+        assert_eq!(counts.len(), 12);
+        for color in [Color::White, Color::Black] {
+            assert_eq!(counts[&(color, PieceType::Pawn)], 8);
+            assert_eq!(counts[&(color, PieceType::Knight)], 2);
+            assert_eq!(counts[&(color, PieceType::Bishop)], 2);
+            assert_eq!(counts[&(color, PieceType::Rook)], 2);
+            assert_eq!(counts[&(color, PieceType::Queen)], 1);
+            assert_eq!(counts[&(color, PieceType::King)], 1);
+        }
+
+        // A rook and two pawns against a knight
+        let board = Board::from_fen("4k3/8/4n3/8/8/8/5PP1/3R2K1").unwrap();
+        let counts = board.piece_counts();
+
+        assert_eq!(counts[&(Color::White, PieceType::Rook)], 1);
+        assert_eq!(counts[&(Color::White, PieceType::Pawn)], 2);
+        assert_eq!(counts[&(Color::White, PieceType::Queen)], 0);
+        assert_eq!(counts[&(Color::Black, PieceType::Knight)], 1);
+        assert_eq!(counts[&(Color::Black, PieceType::Pawn)], 0);
+        assert_eq!(board.piece_count(Color::Black, PieceType::King), 1);
+    }
+
+    #[test]
+    pub fn occupancy_of_empty_own_and_enemy_tiles() {
+        let board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3").unwrap();
+
+        // e4 has a white pawn, d5 a black pawn and e5 is empty
+        assert!(!board.is_empty(4, 3));
+        assert!(board.is_occupied_by(4, 3, Color::White));
+        assert!(!board.is_occupied_by(4, 3, Color::Black));
+        assert!(board.is_occupied_by(3, 4, Color::Black));
+        assert!(!board.is_occupied_by(3, 4, Color::White));
+        assert!(board.is_empty(4, 4));
+        assert!(!board.is_occupied_by(4, 4, Color::White));
+        assert!(!board.is_occupied_by(4, 4, Color::Black));
+        assert!(board.is_empty(8, 0));
+
+        assert_eq!(board.occupancy(Color::White).count(), 2);
+        assert!(board.occupancy(Color::White).is_set(4, 3));
+        assert!(board.occupancy(Color::Black).is_set(3, 4));
+        assert!(!board.occupancy(Color::Black).is_set(4, 3));
+    }
+
+    #[test]
+    pub fn get_tile_outside_the_board_is_none() {
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+
+        assert_eq!(board.get_tile(8, 0), None);
+        assert_eq!(board.get_tile(0, 255), None);
+        assert_eq!(board.get_tile(8, 255), None);
+        assert_eq!(board.get_tile(usize::MAX, 7), None);
+        assert!(board.get_tile(7, 7).is_some());
+    }
+
+    #[test]
+    pub fn index_should_be_same_as_get_tile() {
+        let board =
+            Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1").unwrap();
+
+        for (x, y, tile) in [
+            (0, 7, "a8"),
+            (7, 7, "h8"),
+            (0, 0, "a1"),
+            (7, 0, "h1"),
+            (4, 3, "e4"),
+            (3, 2, "d3"),
+        ] {
+            assert_eq!(board[(x, y)], board.get_tile(x, y));
+            assert_eq!(board.at(tile), board.get_tile(x, y));
+        }
+
+        assert_eq!(board.at("i9"), None);
+    }
 }
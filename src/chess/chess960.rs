@@ -0,0 +1,154 @@
+//! Chess960 (Fischer Random) starting positions
+//!
+//! The 960 positions are numbered with the Scharnagl scheme, the same numbering used by
+//! Stockfish and most GUIs. Position 518 is the normal starting position.
+//!
+//! `Game` only knows how to castle with the rooks on the a and h files, so these boards are
+//! mostly useful for generating test positions and displaying starting setups.
+
+use crate::{error::Chess960Error, Board, Color, Piece, PieceType};
+
+/// The amount of Chess960 starting positions
+pub const POSITION_COUNT: u32 = 960;
+
+/// The files of the two knights among the five tiles left after the bishops and queen are placed
+const KNIGHT_FILES: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// Returns the Chess960 starting position with a certain id
+///
+/// Both colors get the same back rank, mirrored, with pawns in front of it.
+///
+/// # Arguments
+/// * `id` - The Scharnagl number of the position, `0..960`
+///
+/// # Returns
+/// * `Result<Board, Chess960Error>` - The board or an error if the id is out of range
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::chess960;
+///
+/// let board = chess960::starting_position(518).unwrap();
+/// assert_eq!(board.fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
+/// ```
+pub fn starting_position(id: u32) -> Result<Board, Chess960Error> {
+    if id >= POSITION_COUNT {
+        return Err(Chess960Error::InvalidId);
+    }
+
+    let back_rank = back_rank(id as usize);
+
+    let mut array = [[None; 8]; 8];
+    for (x, piece_type) in back_rank.into_iter().enumerate() {
+        array[7][x] = Some(Piece {
+            piece_type,
+            color: Color::Black,
+        });
+        array[6][x] = Some(Piece {
+            piece_type: PieceType::Pawn,
+            color: Color::Black,
+        });
+        array[1][x] = Some(Piece {
+            piece_type: PieceType::Pawn,
+            color: Color::White,
+        });
+        array[0][x] = Some(Piece {
+            piece_type,
+            color: Color::White,
+        });
+    }
+
+    Ok(Board::from_array(array))
+}
+
+/// Internal helper that places the pieces of the back rank from a file to h file
+fn back_rank(mut id: usize) -> [PieceType; 8] {
+    let mut rank = [None; 8];
+
+    // The light squared bishop is on an odd file and the dark squared on an even one
+    rank[id % 4 * 2 + 1] = Some(PieceType::Bishop);
+    id /= 4;
+    rank[id % 4 * 2] = Some(PieceType::Bishop);
+    id /= 4;
+
+    place_on_empty(&mut rank, id % 6, PieceType::Queen);
+    id /= 6;
+
+    // Placing the first knight shifts the empty tiles, so the second one is placed first
+    let (first, second) = KNIGHT_FILES[id];
+    place_on_empty(&mut rank, second, PieceType::Knight);
+    place_on_empty(&mut rank, first, PieceType::Knight);
+
+    // The king always ends up between the rooks
+    for piece_type in [PieceType::Rook, PieceType::King, PieceType::Rook] {
+        place_on_empty(&mut rank, 0, piece_type);
+    }
+
+    rank.map(|tile| tile.expect("Every tile is filled"))
+}
+
+/// Internal helper that places a piece on the `n`th empty tile counted from the a file
+fn place_on_empty(rank: &mut [Option<PieceType>; 8], n: usize, piece_type: PieceType) {
+    let tile = rank
+        .iter_mut()
+        .filter(|tile| tile.is_none())
+        .nth(n)
+        .expect("There are always enough empty tiles");
+
+    *tile = Some(piece_type);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn back_rank_fen(id: u32) -> String {
+        let fen = starting_position(id).unwrap().fen();
+        fen.split('/').next_back().unwrap().to_string()
+    }
+
+    #[test]
+    pub fn known_starting_positions() {
+        assert_eq!(back_rank_fen(518), "RNBQKBNR");
+        assert_eq!(back_rank_fen(0), "BBQNNRKR");
+        assert_eq!(back_rank_fen(1), "BQNBNRKR");
+        assert_eq!(back_rank_fen(959), "RKRNNQBB");
+        assert_eq!(
+            starting_position(0).unwrap().fen(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR"
+        );
+    }
+
+    #[test]
+    pub fn every_id_gives_a_different_valid_position() {
+        let mut ranks = (0..POSITION_COUNT).map(back_rank_fen).collect::<Vec<_>>();
+
+        for rank in &ranks {
+            let king = rank.find('K').unwrap();
+            let bishops = rank.match_indices('B').map(|(x, _)| x).collect::<Vec<_>>();
+
+            assert!(rank.find('R').unwrap() < king && king < rank.rfind('R').unwrap());
+            assert_ne!(bishops[0] % 2, bishops[1] % 2);
+        }
+
+        ranks.sort();
+        ranks.dedup();
+        assert_eq!(ranks.len(), 960);
+
+        assert!(matches!(
+            starting_position(960),
+            Err(Chess960Error::InvalidId)
+        ));
+    }
+}
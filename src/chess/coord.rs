@@ -0,0 +1,162 @@
+//! Helpers for converting between board coordinates and algebraic square names
+//!
+//! The board coordinates has x going from the a-file to the h-file and y going from rank 1 to rank
+//! 8, so `(0, 0)` is a1 and `(7, 7)` is h8.
+
+use alloc::{format, string::String, vec::Vec};
+
+/// Parses an algebraic square name like `e4` into board coordinates
+///
+/// # Arguments
+/// * `square` - The square name, file followed by rank
+///
+/// # Returns
+/// * `Option<(usize, usize)>` - The coordinates of the square or None if it isn't a valid square
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::coord;
+///
+/// assert_eq!(coord::from_algebraic("e4"), Some((4, 3)));
+/// assert_eq!(coord::from_algebraic("a1"), Some((0, 0)));
+/// assert_eq!(coord::from_algebraic("i9"), None);
+/// ```
+pub fn from_algebraic(square: &str) -> Option<(usize, usize)> {
+    let mut chars = square.chars();
+
+    let file = chars.next()?;
+    let rank = chars.next()?;
+
+    if chars.next().is_some() {
+        return None;
+    }
+
+    Some((file_from_char(file)?, rank_from_char(rank)?))
+}
+
+/// Returns the algebraic square name of board coordinates
+///
+/// # Arguments
+/// * `pos` - The coordinates of the square
+///
+/// # Returns
+/// * `String` - The square name, e.g `e4`
+pub fn to_algebraic(pos: (usize, usize)) -> String {
+    format!("{}{}", file_to_char(pos.0), rank_to_char(pos.1))
+}
+
+/// Returns the squares strictly between two squares on the same rank, file or diagonal
+///
+/// A slider that gives check can be blocked by moving a piece to one of these squares.
+///
+/// # Arguments
+/// * `a` - The coordinates of the first square
+/// * `b` - The coordinates of the second square
+///
+/// # Returns
+/// * `Option<Vec<(usize, usize)>>` - The squares in order from `a` to `b`, empty if the squares
+///   are next to each other, or None if the squares aren't on the same line or are the same square
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::coord;
+///
+/// // a1 to d4
+/// assert_eq!(coord::between((0, 0), (3, 3)), Some(vec![(1, 1), (2, 2)]));
+/// // a1 to b3
+/// assert_eq!(coord::between((0, 0), (1, 2)), None);
+/// ```
+pub fn between(a: (usize, usize), b: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+    let d_x = b.0 as i32 - a.0 as i32;
+    let d_y = b.1 as i32 - a.1 as i32;
+
+    if a == b || d_x != 0 && d_y != 0 && d_x.abs() != d_y.abs() {
+        return None;
+    }
+
+    let steps = d_x.abs().max(d_y.abs());
+    let dir = (d_x.signum(), d_y.signum());
+
+    Some(
+        (1..steps)
+            .map(|i| {
+                (
+                    (a.0 as i32 + dir.0 * i) as usize,
+                    (a.1 as i32 + dir.1 * i) as usize,
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Internal helper that turns a file letter into a x coordinate
+pub(crate) fn file_from_char(c: char) -> Option<usize> {
+    match c {
+        'a'..='h' => Some(c as usize - 'a' as usize),
+        _ => None,
+    }
+}
+
+/// Internal helper that turns a rank digit into a y coordinate
+pub(crate) fn rank_from_char(c: char) -> Option<usize> {
+    match c {
+        '1'..='8' => Some(c as usize - '1' as usize),
+        _ => None,
+    }
+}
+
+/// Internal helper that turns a x coordinate into a file letter
+pub(crate) fn file_to_char(x: usize) -> char {
+    char::from(b'a' + x as u8)
+}
+
+/// Internal helper that turns a y coordinate into a rank digit
+pub(crate) fn rank_to_char(y: usize) -> char {
+    char::from(b'1' + y as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn algebraic_round_trip() {
+        for x in 0..8 {
+            for y in 0..8 {
+                assert_eq!(from_algebraic(&to_algebraic((x, y))), Some((x, y)));
+            }
+        }
+
+        assert_eq!(to_algebraic((0, 0)), "a1");
+        assert_eq!(to_algebraic((7, 7)), "h8");
+        assert_eq!(to_algebraic((4, 3)), "e4");
+        assert_eq!(from_algebraic("e"), None);
+        assert_eq!(from_algebraic("e44"), None);
+    }
+
+    #[test]
+    pub fn between_aligned_squares() {
+        // Horizontal and vertical, in both directions
+        assert_eq!(between((0, 0), (3, 0)), Some(vec![(1, 0), (2, 0)]));
+        assert_eq!(between((3, 0), (0, 0)), Some(vec![(2, 0), (1, 0)]));
+        assert_eq!(between((4, 7), (4, 4)), Some(vec![(4, 6), (4, 5)]));
+
+        // Both diagonals
+        assert_eq!(between((4, 4), (1, 1)), Some(vec![(3, 3), (2, 2)]));
+        assert_eq!(
+            between((0, 7), (7, 0)),
+            Some(vec![(1, 6), (2, 5), (3, 4), (4, 3), (5, 2), (6, 1)])
+        );
+
+        // Next to each other
+        assert_eq!(between((4, 4), (5, 5)), Some(vec![]));
+        assert_eq!(between((4, 4), (4, 5)), Some(vec![]));
+    }
+
+    #[test]
+    pub fn between_not_aligned_squares() {
+        assert_eq!(between((4, 4), (5, 6)), None);
+        assert_eq!(between((0, 0), (7, 1)), None);
+        assert_eq!(between((3, 3), (3, 3)), None);
+    }
+}
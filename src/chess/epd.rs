@@ -0,0 +1,172 @@
+//! Parsing of EPD (Extended Position Description) records
+//!
+//! EPD is the format most chess test suites are distributed in. A record is the first four fields
+//! of a FEN string followed by a list of operations, e.g:
+//!
+//! `2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";`
+
+use std::collections::HashMap;
+
+use crate::{error::EpdError, Game};
+
+/// Operations whose operands are moves in SAN
+const MOVE_OPERATIONS: &[&str] = &["bm", "am"];
+
+/// Parses an EPD record
+///
+/// The operations are returned as a map from the opcode to its operands, with surrounding quotes
+/// removed from string operands. The moves of the `bm` (best move) and `am` (avoid move)
+/// operations are checked with `Game::parse_san`, so they are guaranteed to be legal in the
+/// returned position.
+///
+/// # Arguments
+/// * `line` - A single EPD record
+///
+/// # Returns
+/// * `Result<(Game, HashMap<String, String>), EpdError>` - The position and the operations or an
+///   error if the record is invalid
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::epd;
+///
+/// let (game, operations) = epd::parse_epd(
+///     "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(operations["id"], "WAC.001");
+/// let best_move = game.parse_san(&operations["bm"]).unwrap();
+/// ```
+pub fn parse_epd(line: &str) -> Result<(Game, HashMap<String, String>), EpdError> {
+    let line = line.trim();
+
+    let mut rest = line;
+    let mut fields = vec![];
+    for _ in 0..4 {
+        rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+
+        if end == 0 {
+            return Err(EpdError::IncorrectAmountOfParts);
+        }
+
+        fields.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    let game = Game::from_fen(&fields.join(" "))?;
+
+    let operations = operations(rest)?;
+
+    for opcode in MOVE_OPERATIONS {
+        if let Some(operands) = operations.get(*opcode) {
+            for san in operands.split_whitespace() {
+                game.parse_san(san)?;
+            }
+        }
+    }
+
+    Ok((game, operations))
+}
+
+/// Internal helper that splits the operations part of an EPD record
+fn operations(part: &str) -> Result<HashMap<String, String>, EpdError> {
+    let mut operations = HashMap::new();
+
+    let mut chars = part.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let opcode = std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace() && *c != ';'))
+            .collect::<String>();
+
+        if opcode.is_empty() {
+            if chars.next().is_none() {
+                break;
+            }
+            // A stray semicolon
+            continue;
+        }
+
+        let mut operands: Vec<String> = vec![];
+        let mut operand = String::new();
+        let mut in_string = false;
+
+        loop {
+            match chars.next() {
+                Some('"') => {
+                    if in_string {
+                        operands.push(std::mem::take(&mut operand));
+                    }
+                    in_string = !in_string;
+                }
+                Some(c) if in_string => operand.push(c),
+                Some(';') => break,
+                Some(c) if c.is_whitespace() => {
+                    if !operand.is_empty() {
+                        operands.push(std::mem::take(&mut operand));
+                    }
+                }
+                Some(c) => operand.push(c),
+                None => {
+                    if in_string {
+                        return Err(EpdError::UnterminatedString);
+                    }
+                    break;
+                }
+            }
+        }
+
+        if !operand.is_empty() {
+            operands.push(operand);
+        }
+
+        operations.insert(opcode, operands.join(" "));
+    }
+
+    Ok(operations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_wac_record() {
+        let (game, operations) = parse_epd(
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";",
+        )
+        .unwrap();
+
+        assert_eq!(
+            game.fen(),
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - -"
+        );
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations["id"], "WAC.001");
+        assert_eq!(operations["bm"], "Qg6");
+
+        let best_move = game.parse_san(&operations["bm"]).unwrap();
+        assert!(game.gen_all_moves().unwrap().contains(&best_move));
+        assert_eq!(best_move.from(), (6, 2));
+        assert_eq!(best_move.to(), (6, 5));
+    }
+
+    #[test]
+    pub fn parse_invalid_records() {
+        assert!(matches!(
+            parse_epd("8/8/8/8 w"),
+            Err(EpdError::IncorrectAmountOfParts)
+        ));
+        assert!(matches!(
+            parse_epd("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Ke2;"),
+            Err(EpdError::InvalidMove(_))
+        ));
+        assert!(matches!(
+            parse_epd("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - id \"WAC"),
+            Err(EpdError::UnterminatedString)
+        ));
+    }
+}
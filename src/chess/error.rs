@@ -1,35 +1,187 @@
-#[derive(thiserror::Error, Debug)]
+//! The errors of the crate
+//!
+//! With the `std` feature the errors implement `std::error::Error` and `Display`, without it they
+//! only implement `Debug`.
+
+use alloc::string::{String, ToString};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum FromFenError {
-    #[error("FEN string has too many or too few slashes")]
-    IncorrectAmountOfSlash,
-    #[error("Unknown character in fen string")]
+    #[cfg_attr(feature = "std", error("FEN board doesn't have exactly 8 ranks"))]
+    WrongRankCount,
+    /// A rank of the board describes more than 8 files, `rank` is the rank number, 8 for the
+    /// first rank of the FEN
+    #[cfg_attr(
+        feature = "std",
+        error("Rank {rank} of the FEN board has more than 8 files")
+    )]
+    RankOverflow { rank: usize },
+    /// A rank of the board describes less than 8 files, `rank` is the rank number, 8 for the
+    /// first rank of the FEN
+    #[cfg_attr(
+        feature = "std",
+        error("Rank {rank} of the FEN board has less than 8 files")
+    )]
+    RankUnderflow { rank: usize },
+    #[cfg_attr(feature = "std", error("Unknown character in fen string"))]
     UnknownCharacter,
-    #[error("FEN string has too many or too few tiles")]
-    IncorrectAmountOfTiles,
-    #[error("FEN string has too many or too few parts")]
+    #[cfg_attr(feature = "std", error("FEN string has too many or too few parts"))]
     IncorrectAmountOfParts,
-    #[error("Unknown turn")]
+    #[cfg_attr(feature = "std", error("Unknown turn"))]
     UnknownTurn,
-    #[error("Repeating characters in castling part")]
+    /// The castling part names a rook file other than a or h, castling with it isn't supported
+    #[cfg_attr(
+        feature = "std",
+        error("Castling with a rook that doesn't start in a corner isn't supported")
+    )]
+    UnsupportedCastlingFile,
+    #[cfg_attr(feature = "std", error("Repeating characters in castling part"))]
     RepeatingCharactersInCastlingPart,
-    #[error("Incorrect length")]
+    #[cfg_attr(feature = "std", error("Incorrect length"))]
     IncorrectLength,
-    #[error("Invalid en passant")]
+    #[cfg_attr(feature = "std", error("Invalid en passant"))]
     InvalidEnPassant,
+    #[cfg_attr(
+        feature = "std",
+        error("The half move clock or full move number isn't a number")
+    )]
+    InvalidMoveCounter,
 }
 
-#[derive(thiserror::Error, Debug)]
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum DecodeError {
+    #[cfg_attr(feature = "std", error("Encoded game doesn't have the right length"))]
+    WrongLength,
+    #[cfg_attr(feature = "std", error("Unknown piece code in encoded game"))]
+    UnknownPiece,
+    #[cfg_attr(feature = "std", error("Unknown flag bits in encoded game"))]
+    UnknownFlags,
+    #[cfg_attr(feature = "std", error("Invalid en passant"))]
+    InvalidEnPassant,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum GameBuildError {
+    #[cfg_attr(feature = "std", error("Each color must have exactly one king"))]
+    IncorrectAmountOfKings,
+    #[cfg_attr(feature = "std", error("Pawns can't stand on the first or last rank"))]
+    PawnOnBackRank,
+    #[cfg_attr(feature = "std", error("The color that just moved is in check"))]
+    OpponentInCheck,
+    #[cfg_attr(
+        feature = "std",
+        error("A castling right is set without the king and rook on their starting tiles")
+    )]
+    InvalidCastlingRights,
+    #[cfg_attr(feature = "std", error("Invalid en passant"))]
+    InvalidEnPassant,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum GameApplyMoveError {
-    #[error("The move is not valid for this game")]
+    #[cfg_attr(feature = "std", error("The move is not valid for this game"))]
     InvalidMove,
 }
 
-#[derive(thiserror::Error, Debug)]
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum ParsePieceError {
-    #[error("Provided string is too long")]
+    #[cfg_attr(feature = "std", error("Provided string is too long"))]
     StringTooLong,
-    #[error("Provided string is empty")]
+    #[cfg_attr(feature = "std", error("Provided string is empty"))]
     StringEmpty,
-    #[error("Unknown character piece")]
+    #[cfg_attr(feature = "std", error("Unknown character piece"))]
     UnknownCharacterPiece,
 }
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum Chess960Error {
+    #[cfg_attr(feature = "std", error("Chess960 position ids go from 0 to 959"))]
+    InvalidId,
+}
+
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug)]
+pub enum PolyglotError {
+    #[error("Failed to read the book")]
+    Io(#[from] std::io::Error),
+    #[error("Book length is not a multiple of the entry size")]
+    IncorrectLength,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum MoveError {
+    /// The text isn't a move at all, `reason` tells what is wrong with it
+    #[cfg_attr(feature = "std", error("Couldn't parse the move: {reason}"))]
+    ParseFailed { reason: String },
+    #[cfg_attr(feature = "std", error("The move is not legal in this position"))]
+    Illegal,
+    #[cfg_attr(feature = "std", error("The move matches more than one legal move"))]
+    Ambiguous,
+    #[cfg_attr(
+        feature = "std",
+        error("There is no piece on the tile the move starts from")
+    )]
+    NoPieceOnSource,
+    #[cfg_attr(
+        feature = "std",
+        error("The piece on the tile the move starts from belongs to the other color")
+    )]
+    WrongColorPiece,
+    #[cfg_attr(
+        feature = "std",
+        error("A pawn moving to the last rank must be given a piece to promote to")
+    )]
+    PromotionRequired,
+    /// The `+` or `#` after a move doesn't match if the move gives check or checkmate
+    #[cfg_attr(
+        feature = "std",
+        error("The check or checkmate suffix doesn't match the move")
+    )]
+    WrongCheckSuffix,
+}
+
+impl MoveError {
+    /// Internal helper that creates a [`MoveError::ParseFailed`]
+    pub(crate) fn parse_failed(reason: &str) -> MoveError {
+        MoveError::ParseFailed {
+            reason: reason.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug)]
+pub enum EpdError {
+    #[error("EPD record has too few fields")]
+    IncorrectAmountOfParts,
+    #[error("Invalid position: {0}")]
+    InvalidPosition(#[from] FromFenError),
+    #[error("Unterminated string in operation")]
+    UnterminatedString,
+    #[error("Invalid move in operation: {0}")]
+    InvalidMove(#[from] MoveError),
+}
+
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug)]
+pub enum PgnError {
+    #[error("Tag is missing the closing bracket")]
+    UnterminatedTag,
+    #[error("Tag isn't a name followed by a quoted value")]
+    InvalidTag,
+    #[error("Comment is missing the closing brace")]
+    UnterminatedComment,
+    #[error("Variation is missing the closing parenthesis")]
+    UnterminatedVariation,
+    #[error("Invalid FEN tag: {0}")]
+    InvalidFen(#[from] FromFenError),
+    #[error("Failed to read the PGN")]
+    Io(#[from] std::io::Error),
+}
@@ -8,7 +8,7 @@ impl Game {
     ///
     /// # Returns
     /// * `Result<(), Box<dyn Error>>` - A result that holds nothing if the move was applied
-    /// successfully or an error if the move was invalid
+    ///   successfully or an error if the move was invalid
     ///
     /// # Examples
     /// ```
@@ -16,11 +16,28 @@ impl Game {
     ///
     /// let mut game = Game::start_pos();
     /// // Move the pawn on e2 to e3
-    /// game.apply_move(Move::Quiet { from: (4, 6), to: (4, 5) });
+    /// game.apply_move(Move::Quiet { from: (4, 1), to: (4, 2) });
     /// ```
     pub fn apply_move(&mut self, mv: Move) -> Result<(), GameApplyMoveError> {
         self.en_passant = None;
 
+        let (from_x, from_y) = mv.from();
+        let is_pawn_move = self
+            .board
+            .get_tile(from_x, from_y)
+            .is_some_and(|p| p.piece_type == PieceType::Pawn);
+
+        // The half move clock counts moves since the last capture or pawn move
+        if mv.is_capture() || is_pawn_move {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        if self.turn == Color::Black {
+            self.fullmove_number += 1;
+        }
+
         if mv.is_capture() {
             let (c_x, c_y) = mv.capture().expect("This is a capture move");
 
@@ -29,7 +46,6 @@ impl Game {
         }
 
         let (to_x, to_y) = mv.to();
-        let (from_x, from_y) = mv.from();
 
         let piece = self
             .board
@@ -92,24 +108,123 @@ impl Game {
 /// Internal helper that takes a position in one of the corners and removes the castling rights
 /// from that corner
 fn remove_castling_rights_pos(game: &mut Game, pos: (usize, usize)) {
-    if pos == (0, 7) {
-        game.white_queenside_castle = false;
-    } else if pos == (7, 7) {
-        game.white_kingside_castle = false;
-    } else if pos == (0, 0) {
-        game.black_queenside_castle = false;
+    if pos == (0, 0) {
+        game.castling_rights.white_queenside = false;
     } else if pos == (7, 0) {
-        game.black_kingside_castle = false;
+        game.castling_rights.white_kingside = false;
+    } else if pos == (0, 7) {
+        game.castling_rights.black_queenside = false;
+    } else if pos == (7, 7) {
+        game.castling_rights.black_kingside = false;
     }
 }
 
 /// Internal helper that takes a color and removes the castling rights from that color
 fn remove_castling_rights_color(game: &mut Game, color: Color) {
     if color == Color::White {
-        game.white_kingside_castle = false;
-        game.white_queenside_castle = false;
+        game.castling_rights.white_kingside = false;
+        game.castling_rights.white_queenside = false;
     } else {
-        game.black_kingside_castle = false;
-        game.black_queenside_castle = false;
+        game.castling_rights.black_kingside = false;
+        game.castling_rights.black_queenside = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn double_push_allows_en_passant_once() {
+        let mut game = Game::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - -").unwrap();
+
+        game.apply_move(game.parse_san("d5").unwrap()).unwrap();
+        assert_eq!(game.en_passant(), Some((3, 5)));
+
+        let capture = game.parse_san("exd6").unwrap();
+        assert_eq!(
+            capture,
+            Move::Capture {
+                from: (4, 4),
+                to: (3, 5),
+                capture: (3, 4),
+            }
+        );
+
+        let mut captured = game;
+        captured.apply_move(capture).unwrap();
+        assert_eq!(captured.fen(), "4k3/8/3P4/8/8/8/8/4K3 b - -");
+        assert_eq!(captured.en_passant(), None);
+
+        // Any other move clears en passant, so the capture isn't possible later
+        game.apply_move(game.parse_san("Kd2").unwrap()).unwrap();
+        game.apply_move(game.parse_san("Kd7").unwrap()).unwrap();
+        assert_eq!(game.en_passant(), None);
+        assert!(game.parse_san("exd6").is_err());
+    }
+
+    #[test]
+    pub fn knight_move_clears_en_passant() {
+        let mut game = Game::start_pos();
+
+        // e2e4 sets the tile behind the pawn, e3 or (4, 2)
+        game.apply_move(game.parse_san("e4").unwrap()).unwrap();
+        assert_eq!(game.en_passant(), Some((4, 2)));
+        assert_eq!(
+            game.fen(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3"
+        );
+
+        game.apply_move(game.parse_san("Nf6").unwrap()).unwrap();
+        assert_eq!(game.en_passant(), None);
+
+        game.apply_move(game.parse_san("d4").unwrap()).unwrap();
+        game.apply_move(game.parse_san("c5").unwrap()).unwrap();
+        assert_eq!(game.en_passant(), Some((2, 5)));
+
+        game.apply_move(game.parse_san("Nc3").unwrap()).unwrap();
+        assert_eq!(game.en_passant(), None);
+        assert!(game.fen().ends_with(" -"));
+    }
+
+    #[test]
+    pub fn single_push_doesnt_set_en_passant() {
+        let mut game = Game::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - -").unwrap();
+
+        game.apply_move(game.parse_san("d6").unwrap()).unwrap();
+        assert_eq!(game.en_passant(), None);
+    }
+
+    #[test]
+    pub fn capturing_a_rook_removes_castling_rights() {
+        // The bishop captures the rook on h8
+        let mut game = Game::from_fen("r3k2r/8/8/8/8/8/1B6/R3K2R w KQkq -").unwrap();
+
+        game.apply_move(game.parse_san("Bxh8").unwrap()).unwrap();
+        assert!(!game.castling_rights().black_kingside);
+        assert_eq!(game.fen(), "r3k2B/8/8/8/8/8/8/R3K2R b KQq -");
+
+        // The knight captures the rook on a1
+        let mut game = Game::from_fen("r3k2r/8/8/8/8/8/2n5/R3K2R b KQkq -").unwrap();
+
+        game.apply_move(game.parse_san("Nxa1").unwrap()).unwrap();
+        assert_eq!(game.fen(), "r3k2r/8/8/8/8/8/8/n3K2R w Kkq -");
+    }
+
+    #[test]
+    pub fn move_counters_update() {
+        let mut game = Game::start_pos();
+
+        for (san, halfmove_clock, fullmove_number) in [
+            ("Nf3", 1, 1),
+            ("Nf6", 2, 2),
+            ("e4", 0, 2),
+            ("Nxe4", 0, 3),
+            ("Bc4", 1, 3),
+        ] {
+            game.apply_move(game.parse_san(san).unwrap()).unwrap();
+            assert_eq!(game.halfmove_clock(), halfmove_clock);
+            assert_eq!(game.fullmove_number(), fullmove_number);
+        }
     }
 }
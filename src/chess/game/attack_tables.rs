@@ -0,0 +1,100 @@
+use super::gen_pseudo_legal_moves::{KING_QUEEN_DIRS, KNIGHT_DIRS};
+
+/// The tiles a knight attacks from each tile, bit `y * 8 + x` is set for every attacked tile
+pub(super) const KNIGHT_ATTACKS: [u64; 64] = gen_leaper_attacks(KNIGHT_DIRS);
+/// The tiles a king attacks from each tile, bit `y * 8 + x` is set for every attacked tile
+pub(super) const KING_ATTACKS: [u64; 64] = gen_leaper_attacks(KING_QUEEN_DIRS);
+
+/// The tiles a pawn attacks from each tile, indexed by `Color as usize` and then the tile
+pub(super) const PAWN_ATTACKS: [[u64; 64]; 2] = [
+    gen_leaper_attacks(&[(-1, 1), (1, 1)]),
+    gen_leaper_attacks(&[(-1, -1), (1, -1)]),
+];
+
+/// Internal helper that builds the attack table for a piece that jumps a fixed offset
+const fn gen_leaper_attacks(dirs: &[(i32, i32)]) -> [u64; 64] {
+    let mut table = [0; 64];
+
+    let mut i = 0;
+    while i < 64 {
+        let x = (i % 8) as i32;
+        let y = (i / 8) as i32;
+
+        let mut d = 0;
+        while d < dirs.len() {
+            let c_x = x + dirs[d].0;
+            let c_y = y + dirs[d].1;
+
+            if c_x >= 0 && c_x < 8 && c_y >= 0 && c_y < 8 {
+                table[i] |= 1 << (c_y * 8 + c_x);
+            }
+
+            d += 1;
+        }
+
+        i += 1;
+    }
+
+    table
+}
+
+/// Internal helper that iterates over the tiles set in an attack mask
+pub(super) fn tiles(mut mask: u64) -> impl Iterator<Item = (usize, usize)> {
+    core::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
+
+        let i = mask.trailing_zeros() as usize;
+        mask &= mask - 1;
+
+        Some((i % 8, i / 8))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Brute force reference, tries every offset without any table
+    fn reference(x: usize, y: usize, dirs: &[(i32, i32)]) -> Vec<(usize, usize)> {
+        let mut attacks = dirs
+            .iter()
+            .map(|(d_x, d_y)| (x as i32 + d_x, y as i32 + d_y))
+            .filter(|(c_x, c_y)| (0..8).contains(c_x) && (0..8).contains(c_y))
+            .map(|(c_x, c_y)| (c_x as usize, c_y as usize))
+            .collect::<Vec<_>>();
+        attacks.sort_by_key(|(c_x, c_y)| c_y * 8 + c_x);
+        attacks
+    }
+
+    #[test]
+    pub fn tables_match_brute_force() {
+        for i in 0..64 {
+            let (x, y) = (i % 8, i / 8);
+
+            assert_eq!(
+                tiles(KNIGHT_ATTACKS[i]).collect::<Vec<_>>(),
+                reference(x, y, KNIGHT_DIRS)
+            );
+            assert_eq!(
+                tiles(KING_ATTACKS[i]).collect::<Vec<_>>(),
+                reference(x, y, KING_QUEEN_DIRS)
+            );
+        }
+
+        // A white pawn on e2 attacks d3 and f3, a black pawn on e7 attacks d6 and f6
+        assert_eq!(
+            tiles(PAWN_ATTACKS[0][8 + 4]).collect::<Vec<_>>(),
+            vec![(3, 2), (5, 2)]
+        );
+        assert_eq!(
+            tiles(PAWN_ATTACKS[1][6 * 8 + 4]).collect::<Vec<_>>(),
+            vec![(3, 5), (5, 5)]
+        );
+
+        // A knight in the corner only attacks two tiles, in the middle eight
+        assert_eq!(KNIGHT_ATTACKS[0].count_ones(), 2);
+        assert_eq!(KNIGHT_ATTACKS[4 * 8 + 4].count_ones(), 8);
+    }
+}
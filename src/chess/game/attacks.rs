@@ -0,0 +1,293 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{Board, Color, Game, Piece, PieceType};
+
+use super::attack_tables::{self, KING_ATTACKS, KNIGHT_ATTACKS, PAWN_ATTACKS};
+use super::magic;
+
+impl Game {
+    /// Returns the pieces that are giving check to the current turn
+    ///
+    /// # Returns
+    /// * `Vec<(usize, usize)>` - The positions of all the enemy pieces attacking the king of the
+    ///   current turn. Two positions means double check, where only the king is allowed to move
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::from_fen("4k3/8/8/8/8/8/8/4R1K1 b - -").unwrap();
+    /// assert_eq!(game.checkers(), vec![(4, 0)]);
+    /// ```
+    pub fn checkers(&self) -> Vec<(usize, usize)> {
+        self.checkers_of(self.turn)
+    }
+
+    /// Returns the pieces that are giving check to the king of a color
+    ///
+    /// # Arguments
+    /// * `color` - The color of the king
+    ///
+    /// # Returns
+    /// * `Vec<(usize, usize)>` - The positions of all the enemy pieces attacking the king, empty
+    ///   if the color has no king
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Game};
+    ///
+    /// let game = Game::from_fen("4k3/8/5N2/8/8/8/8/4R1K1 b - -").unwrap();
+    /// assert_eq!(game.checkers_of(Color::Black), vec![(4, 0), (5, 5)]);
+    /// assert_eq!(game.checkers_of(Color::White), vec![]);
+    /// ```
+    pub fn checkers_of(&self, color: Color) -> Vec<(usize, usize)> {
+        match self.board.get_king_pos(color) {
+            Some((x, y)) => self.attackers(x, y, color.opposite()),
+            None => vec![],
+        }
+    }
+
+    /// Returns how many pieces of a color attack each tile
+    ///
+    /// A piece attacks the tiles it could capture on, so pawns attack diagonally and the tiles
+    /// behind a blocker aren't attacked. Pieces of the same color count as attacked, they are
+    /// defended.
+    ///
+    /// # Arguments
+    /// * `color` - The color of the attacking pieces
+    ///
+    /// # Returns
+    /// * `[[u8; 8]; 8]` - The number of attackers indexed as `map[y][x]`, row 0 is rank 1 like in
+    ///   [`crate::Board::to_array`]
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Game};
+    ///
+    /// let map = Game::start_pos().control_map(Color::White);
+    ///
+    /// // f3 is attacked by the knight on g1 and the pawns on e2 and g2
+    /// assert_eq!(map[2][5], 3);
+    /// ```
+    pub fn control_map(&self, color: Color) -> [[u8; 8]; 8] {
+        let mut map = [[0; 8]; 8];
+
+        for (y, row) in map.iter_mut().enumerate() {
+            for (x, count) in row.iter_mut().enumerate() {
+                *count = self.attackers_mask(x, y, color).count_ones() as u8;
+            }
+        }
+
+        map
+    }
+
+    /// Internal helper that returns the positions of all pieces of a color that attacks a tile
+    pub(crate) fn attackers(&self, x: usize, y: usize, color: Color) -> Vec<(usize, usize)> {
+        self.board.attackers(x, y, color)
+    }
+
+    /// Internal helper that returns the bitboard of all pieces of a color that attacks a tile
+    pub(crate) fn attackers_mask(&self, x: usize, y: usize, color: Color) -> u64 {
+        self.attackers_mask_with(y * 8 + x, color, self.board.occupied())
+    }
+
+    /// Internal helper that returns the bitboard of all pieces of a color that attacks a tile, as
+    /// if only the pieces in `occupied` were on the board, see [`Board::attackers_mask_with`]
+    pub(crate) fn attackers_mask_with(&self, tile: usize, color: Color, occupied: u64) -> u64 {
+        self.board.attackers_mask_with(tile, color, occupied)
+    }
+
+    /// Internal helper that checks if a tile is attacked by any piece of a color
+    pub(crate) fn is_tile_attacked(&self, x: usize, y: usize, color: Color) -> bool {
+        self.attackers_mask(x, y, color) != 0
+    }
+}
+
+impl Board {
+    /// Returns the positions of all pieces of a color that attack a tile
+    ///
+    /// A piece attacks the tiles it could capture on, pawns attack diagonally forward and sliders
+    /// are stopped by the first piece in their way. The tile itself doesn't need to be empty or
+    /// hold an enemy piece, so this also gives the defenders of a piece.
+    ///
+    /// # Arguments
+    /// * `x` - The x coordinate of the tile
+    /// * `y` - The y coordinate of the tile
+    /// * `by` - The color of the attacking pieces
+    ///
+    /// # Returns
+    /// * `Vec<(usize, usize)>` - The positions of the attackers, ordered from a8 to h1
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color};
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/5N2/8/R3K3").unwrap();
+    ///
+    /// // The rook and the king attack d1, the knight on f3 doesn't
+    /// assert_eq!(board.attackers(3, 0, Color::White), vec![(0, 0), (4, 0)]);
+    /// ```
+    pub fn attackers(&self, x: usize, y: usize, by: Color) -> Vec<(usize, usize)> {
+        attack_tables::tiles(self.attackers_mask_with(y * 8 + x, by, self.occupied())).collect()
+    }
+
+    /// Internal helper that returns the bitboard of all pieces of a color that attacks a tile, as
+    /// if only the pieces in `occupied` were on the board
+    ///
+    /// Instead of generating moves for every piece this looks outwards from the tile, the attacks
+    /// of every piece type from the tile are masked with the bitboards of the pieces. Removing
+    /// pieces from `occupied` reveals the sliders behind them, which is used to find x-ray
+    /// attackers. Pieces outside `occupied` are never returned.
+    pub(crate) fn attackers_mask_with(&self, tile: usize, color: Color, occupied: u64) -> u64 {
+        // A pawn attacks the same tiles from the tile as a pawn of the other color would attack
+        // from the tile it stands on
+        let leapers = PAWN_ATTACKS[color.opposite() as usize][tile]
+            & self.pieces(PieceType::Pawn, color)
+            | KNIGHT_ATTACKS[tile] & self.pieces(PieceType::Knight, color)
+            | KING_ATTACKS[tile] & self.pieces(PieceType::King, color);
+
+        let queens = self.pieces(PieceType::Queen, color);
+
+        let sliders = magic::rook_attacks(tile, occupied)
+            & (self.pieces(PieceType::Rook, color) | queens)
+            | magic::bishop_attacks(tile, occupied)
+                & (self.pieces(PieceType::Bishop, color) | queens);
+
+        (leapers | sliders) & occupied
+    }
+}
+
+/// Internal helper that returns the tiles a piece attacks from a tile
+///
+/// # Arguments
+/// * `piece` - The piece, pawns attack diagonally forward from the perspective of their color
+/// * `tile` - The tile the piece stands on, `y * 8 + x`
+/// * `occupied` - The pieces that block sliders
+pub(super) fn piece_attacks(piece: Piece, tile: usize, occupied: u64) -> u64 {
+    match piece.piece_type {
+        PieceType::Pawn => PAWN_ATTACKS[piece.color as usize][tile],
+        PieceType::Knight => KNIGHT_ATTACKS[tile],
+        PieceType::King => KING_ATTACKS[tile],
+        PieceType::Bishop => magic::bishop_attacks(tile, occupied),
+        PieceType::Rook => magic::rook_attacks(tile, occupied),
+        PieceType::Queen => {
+            magic::rook_attacks(tile, occupied) | magic::bishop_attacks(tile, occupied)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn checkers_single_check_by_slider() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4R1K1 b - -").unwrap();
+        assert_eq!(game.checkers(), vec![(4, 0)]);
+        assert!(game.is_check());
+
+        let game = Game::from_fen("4k3/8/8/1B6/8/8/8/6K1 b - -").unwrap();
+        assert_eq!(game.checkers(), vec![(1, 4)]);
+
+        // The bishop is blocked, so there is no check
+        let game = Game::from_fen("4k3/3p4/8/1B6/8/8/8/6K1 b - -").unwrap();
+        assert!(game.checkers().is_empty());
+        assert!(!game.is_check());
+    }
+
+    #[test]
+    pub fn checkers_double_check() {
+        let game = Game::from_fen("4k3/8/5N2/8/8/8/8/4R1K1 b - -").unwrap();
+
+        let checkers = game.checkers();
+        assert_eq!(checkers.len(), 2);
+        assert!(checkers.contains(&(4, 0)));
+        assert!(checkers.contains(&(5, 5)));
+
+        // Only the king can move in a double check
+        let moves = game.gen_all_moves().unwrap();
+        assert!(moves.iter().all(|m| m.from() == (4, 7)));
+    }
+
+    #[test]
+    pub fn checkers_after_a_discovered_double_check() {
+        // The knight on e4 moves out of the way of the rook and checks the king itself
+        let mut game = Game::from_fen("4k3/8/8/8/4N3/8/8/4R1K1 w - -").unwrap();
+        assert!(game.checkers_of(Color::Black).is_empty());
+
+        game.apply_move(game.parse_san("Nf6+").unwrap()).unwrap();
+
+        let checkers = game.checkers_of(Color::Black);
+        assert_eq!(checkers.len(), 2);
+        assert!(checkers.contains(&(5, 5)));
+        assert!(checkers.contains(&(4, 0)));
+        assert_eq!(checkers, game.checkers());
+        assert!(game.checkers_of(Color::White).is_empty());
+
+        // Without a king nothing gives check
+        let game = Game::from_fen("8/8/8/8/8/8/8/4R1K1 b - -").unwrap();
+        assert!(game.checkers_of(Color::Black).is_empty());
+    }
+
+    #[test]
+    pub fn blocking_and_capturing_the_checker() {
+        // The rook can block on e5, the knight can block on e5 or capture the checking rook
+        let game = Game::from_fen("4k3/8/8/r7/8/3n4/8/4R1K1 b - -").unwrap();
+
+        let mut moves = game
+            .gen_all_moves()
+            .unwrap()
+            .into_iter()
+            .filter(|m| m.from() != (4, 7))
+            .map(|m| (m.from(), m.to()))
+            .collect::<Vec<_>>();
+        moves.sort();
+
+        assert_eq!(
+            moves,
+            vec![((0, 4), (4, 4)), ((3, 2), (4, 0)), ((3, 2), (4, 4))]
+        );
+    }
+
+    #[test]
+    pub fn board_attackers_of_a_tile() {
+        // e5 is attacked by the bishop on h8, the pawn on d4, the knight on f3 and the rook on e1,
+        // the queen on e6 is black's only attacker
+        let board = Board::from_fen("4k2B/8/4q3/8/3P4/5N2/8/4R1K1").unwrap();
+
+        assert_eq!(
+            board.attackers(4, 4, Color::White),
+            vec![(4, 0), (5, 2), (3, 3), (7, 7)]
+        );
+        assert_eq!(board.attackers(4, 4, Color::Black), vec![(4, 5)]);
+
+        // The queen blocks the rook from e7 and is defended by nothing
+        assert_eq!(board.attackers(4, 6, Color::White), vec![]);
+        assert_eq!(board.attackers(4, 5, Color::Black), vec![]);
+        assert_eq!(board.attackers(4, 5, Color::White), vec![(4, 0)]);
+        assert_eq!(board.attackers(3, 6, Color::Black), vec![(4, 5), (4, 7)]);
+    }
+
+    #[test]
+    pub fn control_map_of_the_start_position() {
+        let game = Game::start_pos();
+        let white = game.control_map(Color::White);
+        let black = game.control_map(Color::Black);
+
+        // d2 is defended by the knight, bishop, queen and king
+        assert_eq!(white[1][3], 4);
+        // d3 by the pawns on c2 and e2
+        assert_eq!(white[2][3], 2);
+        // f6 by the knight on g8 and the pawns on e7 and g7
+        assert_eq!(black[5][5], 3);
+        // The rooks and the middle of the board aren't attacked
+        assert_eq!(white[0][0], 0);
+        assert_eq!(white[3][4], 0);
+        assert_eq!(black[4][3], 0);
+
+        // The sides are symmetric
+        for y in 0..8 {
+            assert_eq!(white[y], black[7 - y]);
+        }
+    }
+}
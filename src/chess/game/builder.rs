@@ -0,0 +1,332 @@
+use crate::{
+    error::GameBuildError, Board, CastleSide, CastlingRights, Color, Game, Piece, PieceType,
+};
+
+/// A builder for setting up a game piece by piece
+///
+/// The builder starts from an empty board with white to move and no castling rights.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Color, GameBuilder, Piece, PieceType};
+///
+/// let game = GameBuilder::new()
+///     .piece(4, 0, Piece { piece_type: PieceType::King, color: Color::White })
+///     .piece(4, 7, Piece { piece_type: PieceType::King, color: Color::Black })
+///     .piece(0, 6, Piece { piece_type: PieceType::Pawn, color: Color::White })
+///     .turn(Color::Black)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(game.fen(), "4k3/P7/8/8/8/8/8/4K3 b - -");
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameBuilder {
+    game: Game,
+    en_passant: Option<(usize, usize)>,
+}
+
+impl GameBuilder {
+    /// Creates a builder with an empty board
+    pub fn new() -> GameBuilder {
+        let mut game = Game::start_pos();
+        game.set_board(Board::from_array([[None; 8]; 8]));
+
+        GameBuilder {
+            game,
+            en_passant: None,
+        }
+    }
+
+    /// Creates a builder that starts from an existing game
+    pub fn from_game(game: Game) -> GameBuilder {
+        GameBuilder {
+            en_passant: game.en_passant(),
+            game,
+        }
+    }
+
+    /// Places a piece on a tile, replacing whatever was there
+    pub fn piece(mut self, x: usize, y: usize, piece: Piece) -> GameBuilder {
+        self.game.board.set_tile(x, y, piece);
+        self
+    }
+
+    /// Removes the piece on a tile
+    pub fn remove(mut self, x: usize, y: usize) -> GameBuilder {
+        self.game.board.remove_tile(x, y);
+        self
+    }
+
+    /// Sets the color to move
+    pub fn turn(mut self, turn: Color) -> GameBuilder {
+        self.game.turn = turn;
+        self
+    }
+
+    /// Sets the castling rights
+    pub fn castling_rights(mut self, castling_rights: CastlingRights) -> GameBuilder {
+        self.game.castling_rights = castling_rights;
+        self
+    }
+
+    /// Sets the en passant tile, the tile a capturing pawn would move to
+    pub fn en_passant(mut self, en_passant: Option<(usize, usize)>) -> GameBuilder {
+        self.en_passant = en_passant;
+        self
+    }
+
+    /// Sets the amount of half moves since the last capture or pawn move
+    pub fn halfmove_clock(mut self, halfmove_clock: u32) -> GameBuilder {
+        self.game.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    /// Sets the number of the current full move
+    pub fn fullmove_number(mut self, fullmove_number: u32) -> GameBuilder {
+        self.game.fullmove_number = fullmove_number;
+        self
+    }
+
+    /// Builds the game and checks that the position is valid, see [`Game::validate`]
+    ///
+    /// # Returns
+    /// * `Result<Game, GameBuildError>` - The game or the reason the position isn't valid
+    pub fn build(self) -> Result<Game, GameBuildError> {
+        let en_passant = self.en_passant;
+        let game = self.build_unchecked();
+
+        // An en passant tile the current turn can't capture on is dropped by the setter
+        if en_passant.is_some() && game.en_passant().is_none() {
+            return Err(GameBuildError::InvalidEnPassant);
+        }
+
+        game.validate()?;
+
+        Ok(game)
+    }
+
+    /// Builds the game without checking that the position is valid
+    pub fn build_unchecked(self) -> Game {
+        self.game.with_en_passant(self.en_passant)
+    }
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        GameBuilder::new()
+    }
+}
+
+impl Game {
+    /// Checks that the position is valid
+    ///
+    /// A position is valid if each color has exactly one king, no pawns stand on the first or
+    /// last rank, the color that just moved isn't in check, every castling right has the king
+    /// and rook on their starting tiles and the en passant pawn is where it should be.
+    ///
+    /// # Returns
+    /// * `Result<(), GameBuildError>` - Nothing or the first problem that was found
+    pub fn validate(&self) -> Result<(), GameBuildError> {
+        for color in [Color::White, Color::Black] {
+            if self.board.pieces(PieceType::King, color).count_ones() != 1 {
+                return Err(GameBuildError::IncorrectAmountOfKings);
+            }
+        }
+
+        const BACK_RANKS: u64 = 0xFF00_0000_0000_00FF;
+        let pawns = self.board.pieces(PieceType::Pawn, Color::White)
+            | self.board.pieces(PieceType::Pawn, Color::Black);
+        if pawns & BACK_RANKS != 0 {
+            return Err(GameBuildError::PawnOnBackRank);
+        }
+
+        if self.can_capture_king(self.turn) {
+            return Err(GameBuildError::OpponentInCheck);
+        }
+
+        for (color, rank) in [(Color::White, 0), (Color::Black, 7)] {
+            for (side, rook_x) in [(CastleSide::KingSide, 7), (CastleSide::QueenSide, 0)] {
+                let king = Piece {
+                    piece_type: PieceType::King,
+                    color,
+                };
+                let rook = Piece {
+                    piece_type: PieceType::Rook,
+                    color,
+                };
+
+                if self.has_castling_right(color, side)
+                    && (self.board.get_tile(4, rank) != Some(king)
+                        || self.board.get_tile(rook_x, rank) != Some(rook))
+                {
+                    return Err(GameBuildError::InvalidCastlingRights);
+                }
+            }
+        }
+
+        if let Some((x, y)) = self.en_passant {
+            let pawn = Piece {
+                piece_type: PieceType::Pawn,
+                color: self.turn.opposite(),
+            };
+            let (target_x, target_y) = self.en_passant().expect("En passant is set");
+            // The tile the pawn moved two tiles from, on the other side of the target tile
+            let origin_y = 2 * target_y - y;
+
+            if self.board.get_tile(x, y) != Some(pawn)
+                || !self.board.is_empty(target_x, target_y)
+                || !self.board.is_empty(x, origin_y)
+            {
+                return Err(GameBuildError::InvalidEnPassant);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn build_the_start_position() {
+        let mut builder = GameBuilder::new();
+
+        let back_rank = [
+            PieceType::Rook,
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Queen,
+            PieceType::King,
+            PieceType::Bishop,
+            PieceType::Knight,
+            PieceType::Rook,
+        ];
+
+        for (color, back, front) in [(Color::White, 0, 1), (Color::Black, 7, 6)] {
+            for (x, piece_type) in back_rank.into_iter().enumerate() {
+                builder = builder.piece(x, back, Piece { piece_type, color }).piece(
+                    x,
+                    front,
+                    Piece {
+                        piece_type: PieceType::Pawn,
+                        color,
+                    },
+                );
+            }
+        }
+
+        let game = builder
+            .castling_rights(CastlingRights {
+                white_kingside: true,
+                white_queenside: true,
+                black_kingside: true,
+                black_queenside: true,
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            game,
+            Game::default(),
+            "{:?}",
+            game.fen_diff(&Game::default())
+        );
+    }
+
+    #[test]
+    pub fn build_rejects_invalid_positions() {
+        let king = |color| Piece {
+            piece_type: PieceType::King,
+            color,
+        };
+        let kings =
+            GameBuilder::new()
+                .piece(4, 0, king(Color::White))
+                .piece(4, 7, king(Color::Black));
+
+        assert!(kings.clone().build().is_ok());
+        assert!(matches!(
+            GameBuilder::new().piece(4, 0, king(Color::White)).build(),
+            Err(GameBuildError::IncorrectAmountOfKings)
+        ));
+        assert!(matches!(
+            kings
+                .clone()
+                .piece(
+                    0,
+                    0,
+                    Piece {
+                        piece_type: PieceType::Pawn,
+                        color: Color::White
+                    }
+                )
+                .build(),
+            Err(GameBuildError::PawnOnBackRank)
+        ));
+        // Black is in check from the rook but it's white to move
+        assert!(matches!(
+            kings
+                .clone()
+                .piece(
+                    4,
+                    4,
+                    Piece {
+                        piece_type: PieceType::Rook,
+                        color: Color::White
+                    }
+                )
+                .build(),
+            Err(GameBuildError::OpponentInCheck)
+        ));
+        assert!(matches!(
+            kings
+                .clone()
+                .castling_rights(CastlingRights {
+                    white_kingside: true,
+                    ..CastlingRights::default()
+                })
+                .build(),
+            Err(GameBuildError::InvalidCastlingRights)
+        ));
+        assert!(matches!(
+            kings.clone().en_passant(Some((3, 5))).build(),
+            Err(GameBuildError::InvalidEnPassant)
+        ));
+
+        // The pawn on d5 couldn't have moved two tiles with a piece on d7 or d6
+        let pawn_on_d5 =
+            GameBuilder::from_game(Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - -").unwrap());
+        let knight = Piece {
+            piece_type: PieceType::Knight,
+            color: Color::Black,
+        };
+        assert!(pawn_on_d5.clone().en_passant(Some((3, 5))).build().is_ok());
+        for y in [6, 5] {
+            assert!(matches!(
+                pawn_on_d5
+                    .clone()
+                    .piece(3, y, knight)
+                    .en_passant(Some((3, 5)))
+                    .build(),
+                Err(GameBuildError::InvalidEnPassant)
+            ));
+        }
+    }
+
+    #[test]
+    pub fn build_with_en_passant_and_counters() {
+        let game = GameBuilder::from_game(Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - -").unwrap())
+            .en_passant(Some((3, 5)))
+            .halfmove_clock(0)
+            .fullmove_number(40)
+            .build()
+            .unwrap();
+
+        let expected = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 40").unwrap();
+        assert_eq!(game, expected, "{:?}", game.fen_diff(&expected));
+        assert_eq!(game.fullmove_number(), 40);
+    }
+}
@@ -0,0 +1,253 @@
+use alloc::vec::Vec;
+
+use crate::{error::DecodeError, Board, CastlingRights, Color, Game, Piece, PieceType};
+
+use super::fen::en_passant_pawn;
+
+impl Game {
+    /// The length of [`Game::to_bytes`]
+    pub const BYTES_LEN: usize = 42;
+
+    /// Encodes the game in a compact binary format of [`Game::BYTES_LEN`] bytes
+    ///
+    /// The layout is stable between versions:
+    ///
+    /// | Bytes    | Content |
+    /// |----------|---------|
+    /// | `0..32`  | One nibble per tile from a8 to h1 like in a FEN string, tile `i = (7 - y) * 8 + x` in byte `i / 2`, the low nibble for even tiles |
+    /// | `32`     | Bit 0 is set if it's black to move, bits 1 to 4 are the castling rights `K`, `Q`, `k` and `q` |
+    /// | `33`     | The en passant tile like in a FEN string as `(7 - y) * 8 + x`, or 255 if there is none |
+    /// | `34..38` | The half move clock, a little endian `u32` |
+    /// | `38..42` | The full move number, a little endian `u32` |
+    ///
+    /// A tile nibble is 0 for an empty tile, 1 to 6 for a white pawn, knight, bishop, rook, queen
+    /// and king, and the same plus 8 for the black pieces.
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - The encoded game
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::start_pos();
+    /// let bytes = game.to_bytes();
+    ///
+    /// assert_eq!(bytes.len(), Game::BYTES_LEN);
+    /// assert_eq!(Game::from_bytes(&bytes).unwrap(), game);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Game::BYTES_LEN);
+
+        // The ranks are written from rank 8 down, like in a FEN string
+        for y in (0..8).rev() {
+            for pair in self.board.tiles[y * 8..y * 8 + 8].chunks(2) {
+                bytes.push(piece_code(pair[0]) | piece_code(pair[1]) << 4);
+            }
+        }
+
+        let rights = self.castling_rights;
+        let flags = [
+            self.turn == Color::Black,
+            rights.white_kingside,
+            rights.white_queenside,
+            rights.black_kingside,
+            rights.black_queenside,
+        ];
+        bytes.push(
+            flags
+                .iter()
+                .enumerate()
+                .fold(0, |byte, (i, flag)| byte | (*flag as u8) << i),
+        );
+
+        bytes.push(match self.en_passant() {
+            Some((x, y)) => ((7 - y) * 8 + x) as u8,
+            None => NO_EN_PASSANT,
+        });
+
+        bytes.extend_from_slice(&self.halfmove_clock.to_le_bytes());
+        bytes.extend_from_slice(&self.fullmove_number.to_le_bytes());
+
+        bytes
+    }
+
+    /// Decodes a game encoded with [`Game::to_bytes`]
+    ///
+    /// # Arguments
+    /// * `bytes` - The encoded game, exactly [`Game::BYTES_LEN`] bytes
+    ///
+    /// # Returns
+    /// * `Result<Game, DecodeError>` - The game or an error if the bytes aren't a valid encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Game, DecodeError> {
+        if bytes.len() != Game::BYTES_LEN {
+            return Err(DecodeError::WrongLength);
+        }
+
+        let mut array = [[None; 8]; 8];
+        for (i, byte) in bytes[..32].iter().enumerate() {
+            for (j, code) in [byte & 0x0f, byte >> 4].into_iter().enumerate() {
+                let tile = i * 2 + j;
+                array[7 - tile / 8][tile % 8] = piece_from_code(code)?;
+            }
+        }
+        let board = Board::from_array(array);
+
+        let flags = bytes[32];
+        if flags >> 5 != 0 {
+            return Err(DecodeError::UnknownFlags);
+        }
+        let flag = |i: u8| flags & 1 << i != 0;
+
+        let turn = if flag(0) { Color::Black } else { Color::White };
+
+        let en_passant = match bytes[33] {
+            NO_EN_PASSANT => None,
+            tile if tile < 64 => {
+                let tile = (tile as usize % 8, 7 - tile as usize / 8);
+                Some(en_passant_pawn(&board, turn, tile).ok_or(DecodeError::InvalidEnPassant)?)
+            }
+            _ => return Err(DecodeError::InvalidEnPassant),
+        };
+
+        let counter = |start: usize| {
+            u32::from_le_bytes(
+                bytes[start..start + 4]
+                    .try_into()
+                    .expect("The length is checked above"),
+            )
+        };
+
+        Ok(Game {
+            board,
+            turn,
+            en_passant,
+            castling_rights: CastlingRights {
+                white_kingside: flag(1),
+                white_queenside: flag(2),
+                black_kingside: flag(3),
+                black_queenside: flag(4),
+            },
+            halfmove_clock: counter(34),
+            fullmove_number: counter(38),
+        })
+    }
+}
+
+/// The en passant byte when there is no en passant tile
+const NO_EN_PASSANT: u8 = 255;
+
+/// Internal helper that returns the nibble of a tile
+fn piece_code(tile: Option<Piece>) -> u8 {
+    match tile {
+        Some(piece) => {
+            // The codes start at 1 in the order of PieceType::all, 0 is an empty tile
+            let code = piece.piece_type as u8 + 1;
+
+            match piece.color {
+                Color::White => code,
+                Color::Black => code | 8,
+            }
+        }
+        None => 0,
+    }
+}
+
+/// Internal helper that returns the tile of a nibble
+fn piece_from_code(code: u8) -> Result<Option<Piece>, DecodeError> {
+    if code == 0 {
+        return Ok(None);
+    }
+
+    let color = if code & 8 == 0 {
+        Color::White
+    } else {
+        Color::Black
+    };
+
+    let piece_type = *PieceType::all()
+        .get(((code & 7) as usize).wrapping_sub(1))
+        .ok_or(DecodeError::UnknownPiece)?;
+
+    Ok(Some(Piece { piece_type, color }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn bytes_round_trip() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w Kq f6 0 3",
+            "4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 70000",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            let bytes = game.to_bytes();
+
+            assert_eq!(bytes.len(), Game::BYTES_LEN);
+            assert_eq!(Game::from_bytes(&bytes).unwrap(), game, "{}", fen);
+        }
+    }
+
+    #[test]
+    pub fn bytes_layout() {
+        let game = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b Kq e3 3 40").unwrap();
+        let bytes = game.to_bytes();
+
+        // The black king on e8 is tile 4, the black pawn on d4 tile 35, the white pawn on e4 tile
+        // 36 and the white king on e1 tile 60
+        assert_eq!(bytes[2], 0x0e);
+        assert_eq!(bytes[17], 0x90);
+        assert_eq!(bytes[18], 0x01);
+        assert_eq!(bytes[30], 0x06);
+        assert_eq!(bytes[32], 0b10011);
+        assert_eq!(bytes[33], 44);
+        assert_eq!(&bytes[34..], &[3, 0, 0, 0, 40, 0, 0, 0]);
+    }
+
+    #[test]
+    pub fn invalid_bytes() {
+        let bytes = Game::start_pos().to_bytes();
+
+        assert!(matches!(
+            Game::from_bytes(&bytes[..41]),
+            Err(DecodeError::WrongLength)
+        ));
+
+        let mut invalid = bytes.clone();
+        invalid[20] = 0x07;
+        assert!(matches!(
+            Game::from_bytes(&invalid),
+            Err(DecodeError::UnknownPiece)
+        ));
+
+        let mut invalid = bytes.clone();
+        invalid[32] |= 1 << 6;
+        assert!(matches!(
+            Game::from_bytes(&invalid),
+            Err(DecodeError::UnknownFlags)
+        ));
+
+        // No pawn in front of e3
+        let mut invalid = bytes.clone();
+        invalid[33] = 44;
+        assert!(matches!(
+            Game::from_bytes(&invalid),
+            Err(DecodeError::InvalidEnPassant)
+        ));
+
+        let mut invalid = bytes;
+        invalid[33] = 64;
+        assert!(matches!(
+            Game::from_bytes(&invalid),
+            Err(DecodeError::InvalidEnPassant)
+        ));
+    }
+}
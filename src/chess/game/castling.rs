@@ -0,0 +1,348 @@
+use crate::{Board, Color, Game, Piece, PieceType};
+
+/// The castling rights of both colors
+///
+/// A right is lost when the king or the rook moves, or when the rook is captured.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+}
+
+/// The side of the board a king castles to
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CastleSide {
+    /// Castling with the rook on the h file, `O-O`
+    KingSide,
+    /// Castling with the rook on the a file, `O-O-O`
+    QueenSide,
+}
+
+impl CastlingRights {
+    /// Returns if a color has the right to castle to a side
+    pub fn get(&self, color: Color, side: CastleSide) -> bool {
+        match (color, side) {
+            (Color::White, CastleSide::KingSide) => self.white_kingside,
+            (Color::White, CastleSide::QueenSide) => self.white_queenside,
+            (Color::Black, CastleSide::KingSide) => self.black_kingside,
+            (Color::Black, CastleSide::QueenSide) => self.black_queenside,
+        }
+    }
+
+    /// Sets if a color has the right to castle to a side
+    pub fn set(&mut self, color: Color, side: CastleSide, value: bool) {
+        let right = match (color, side) {
+            (Color::White, CastleSide::KingSide) => &mut self.white_kingside,
+            (Color::White, CastleSide::QueenSide) => &mut self.white_queenside,
+            (Color::Black, CastleSide::KingSide) => &mut self.black_kingside,
+            (Color::Black, CastleSide::QueenSide) => &mut self.black_queenside,
+        };
+
+        *right = value;
+    }
+
+    /// Guesses the castling rights from the pieces on a board
+    ///
+    /// A right is set if the king and the rook of that side are on their starting tiles. The board
+    /// doesn't tell if they have moved and come back, so this can give rights that a real game
+    /// would have lost.
+    ///
+    /// # Arguments
+    /// * `board` - The board to look at
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, CastlingRights, Color, CastleSide};
+    ///
+    /// let board = Board::from_fen("r3k3/8/8/8/8/8/8/4K2R").unwrap();
+    /// let rights = CastlingRights::infer(&board);
+    ///
+    /// assert!(rights.get(Color::White, CastleSide::KingSide));
+    /// assert!(!rights.get(Color::White, CastleSide::QueenSide));
+    /// assert!(rights.get(Color::Black, CastleSide::QueenSide));
+    /// ```
+    pub fn infer(board: &Board) -> CastlingRights {
+        let mut rights = CastlingRights::default();
+
+        for (color, rank) in [(Color::White, 0), (Color::Black, 7)] {
+            let king = Piece {
+                piece_type: PieceType::King,
+                color,
+            };
+            let rook = Piece {
+                piece_type: PieceType::Rook,
+                color,
+            };
+
+            if board.get_tile(4, rank) != Some(king) {
+                continue;
+            }
+
+            for (side, rook_x) in [(CastleSide::KingSide, 7), (CastleSide::QueenSide, 0)] {
+                rights.set(color, side, board.get_tile(rook_x, rank) == Some(rook));
+            }
+        }
+
+        rights
+    }
+
+    /// Returns the castling rights with white and black swapped
+    pub fn mirror(&self) -> CastlingRights {
+        CastlingRights {
+            white_kingside: self.black_kingside,
+            white_queenside: self.black_queenside,
+            black_kingside: self.white_kingside,
+            black_queenside: self.white_queenside,
+        }
+    }
+}
+
+impl Game {
+    /// Returns if a color can castle to a side right now
+    ///
+    /// The same as [`Game::can_castle_kingside`] and [`Game::can_castle_queenside`], use
+    /// [`Game::has_castling_right`] to only check the castling right.
+    ///
+    /// # Arguments
+    /// * `color` - The color to check, this doesn't have to be the current turn
+    /// * `side` - The side to castle to
+    pub fn can_castle(&self, color: Color, side: CastleSide) -> bool {
+        match side {
+            CastleSide::KingSide => self.can_castle_kingside(color),
+            CastleSide::QueenSide => self.can_castle_queenside(color),
+        }
+    }
+
+    /// Returns if a color still has the right to castle to a side
+    ///
+    /// The right is kept until the king or the rook moves, even if castling isn't possible right
+    /// now.
+    ///
+    /// # Arguments
+    /// * `color` - The color to check
+    /// * `side` - The side to castle to
+    pub fn has_castling_right(&self, color: Color, side: CastleSide) -> bool {
+        self.castling_rights.get(color, side)
+    }
+
+    /// Sets the right of a color to castle to a side
+    ///
+    /// This is an unchecked edit, see [`Game::set_castling_rights`]. [`Game::validate`] checks that
+    /// the king and rook are still on their starting tiles.
+    ///
+    /// # Arguments
+    /// * `color` - The color to set the right for
+    /// * `side` - The side to castle to
+    /// * `value` - If the color should have the right
+    pub fn set_castling(&mut self, color: Color, side: CastleSide, value: bool) {
+        self.castling_rights.set(color, side, value);
+    }
+
+    /// Returns if a color can castle kingside right now
+    ///
+    /// This checks more than the castling rights, the king and rook has to be on their starting
+    /// tiles, the tiles between them has to be empty and the king can't be in check, pass through
+    /// an attacked tile or end up on an attacked tile.
+    ///
+    /// # Arguments
+    /// * `color` - The color to check, this doesn't have to be the current turn
+    ///
+    /// # Returns
+    /// * `bool` - If the color can castle kingside
+    pub fn can_castle_kingside(&self, color: Color) -> bool {
+        self.has_castling_right(color, CastleSide::KingSide)
+            && self.castle_path_is_clear(color, 7, &[5, 6], &[4, 5, 6])
+    }
+
+    /// Returns if a color can castle queenside right now
+    ///
+    /// This checks more than the castling rights, the king and rook has to be on their starting
+    /// tiles, the tiles between them has to be empty and the king can't be in check, pass through
+    /// an attacked tile or end up on an attacked tile.
+    ///
+    /// # Arguments
+    /// * `color` - The color to check, this doesn't have to be the current turn
+    ///
+    /// # Returns
+    /// * `bool` - If the color can castle queenside
+    pub fn can_castle_queenside(&self, color: Color) -> bool {
+        self.has_castling_right(color, CastleSide::QueenSide)
+            && self.castle_path_is_clear(color, 0, &[1, 2, 3], &[4, 3, 2])
+    }
+
+    /// Internal helper that checks everything except the castling rights
+    ///
+    /// # Arguments
+    /// * `color` - The color that castles
+    /// * `rook_x` - The x coordinate of the rook
+    /// * `tiles_empty` - The x coordinates of the tiles that has to be empty
+    /// * `tiles_not_attacked` - The x coordinates of the tiles the king is on or passes
+    fn castle_path_is_clear(
+        &self,
+        color: Color,
+        rook_x: usize,
+        tiles_empty: &[usize],
+        tiles_not_attacked: &[usize],
+    ) -> bool {
+        let rank = if color == Color::White { 0 } else { 7 };
+
+        let king = Piece {
+            piece_type: PieceType::King,
+            color,
+        };
+        let rook = Piece {
+            piece_type: PieceType::Rook,
+            color,
+        };
+
+        self.board.get_tile(4, rank) == Some(king)
+            && self.board.get_tile(rook_x, rank) == Some(rook)
+            && tiles_empty.iter().all(|x| self.board.is_empty(*x, rank))
+            && tiles_not_attacked
+                .iter()
+                .all(|x| !self.is_tile_attacked(*x, rank, color.opposite()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn can_castle_when_nothing_is_in_the_way() {
+        let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq -").unwrap();
+
+        assert!(game.can_castle_kingside(Color::White));
+        assert!(game.can_castle_queenside(Color::White));
+        assert!(game.can_castle_kingside(Color::Black));
+        assert!(game.can_castle_queenside(Color::Black));
+    }
+
+    #[test]
+    pub fn cant_castle_without_rights() {
+        let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kq -").unwrap();
+
+        assert!(game.can_castle_kingside(Color::White));
+        assert!(!game.can_castle_queenside(Color::White));
+        assert!(!game.can_castle_kingside(Color::Black));
+        assert!(game.can_castle_queenside(Color::Black));
+    }
+
+    #[test]
+    pub fn cant_castle_through_attacked_tiles() {
+        // The rook on f8 attacks f1, which the white king would pass
+        let game = Game::from_fen("r3kr2/8/8/8/8/8/8/R3K2R w KQq -").unwrap();
+        assert!(!game.can_castle_kingside(Color::White));
+        assert!(game.can_castle_queenside(Color::White));
+
+        // The bishop on f5 attacks c8, where the black king would end up
+        let game = Game::from_fen("r3k2r/8/8/5B2/8/8/8/R3K2R b KQkq -").unwrap();
+        assert!(!game.can_castle_queenside(Color::Black));
+        assert!(game.can_castle_kingside(Color::Black));
+
+        // The tile next to the rook can be attacked, the king never passes it
+        let game = Game::from_fen("rr2k3/8/8/8/8/8/8/R3K2R w KQ -").unwrap();
+        assert!(game.can_castle_queenside(Color::White));
+
+        // The king can't castle out of check
+        let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq -").unwrap();
+        let game_in_check = Game::from_fen("r3k2r/8/8/8/8/8/8/R3R1K1 b kq -").unwrap();
+        assert!(game.can_castle_kingside(Color::Black));
+        assert!(!game_in_check.can_castle_kingside(Color::Black));
+        assert!(!game_in_check.can_castle_queenside(Color::Black));
+    }
+
+    #[test]
+    pub fn cant_castle_when_blocked_or_missing_pieces() {
+        let game = Game::from_fen("rn2k1nr/8/8/8/8/8/8/4K2R w KQkq -").unwrap();
+
+        assert!(!game.can_castle_kingside(Color::Black));
+        assert!(!game.can_castle_queenside(Color::Black));
+        // The rook on a1 is gone, even though the right is still set
+        assert!(!game.can_castle_queenside(Color::White));
+        assert!(game.can_castle_kingside(Color::White));
+    }
+
+    #[test]
+    pub fn infer_castling_rights_from_the_board() {
+        assert_eq!(
+            CastlingRights::infer(Game::start_pos().board()),
+            Game::start_pos().castling_rights()
+        );
+
+        // The h1 rook has moved and the black king isn't on e8
+        let game = Game::from_fen("r2k3r/8/8/8/8/8/8/R3K1R1 w - -").unwrap();
+        assert_eq!(
+            CastlingRights::infer(game.board()),
+            CastlingRights {
+                white_kingside: false,
+                white_queenside: true,
+                black_kingside: false,
+                black_queenside: false,
+            }
+        );
+
+        // Rooks of the other color don't count
+        let game = Game::from_fen("R3k2R/8/8/8/8/8/8/r3K2r w - -").unwrap();
+        assert_eq!(
+            CastlingRights::infer(game.board()),
+            CastlingRights::default()
+        );
+    }
+
+    #[test]
+    pub fn castling_rights_round_trip() {
+        let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w - -").unwrap();
+
+        for color in [Color::White, Color::Black] {
+            for side in [CastleSide::KingSide, CastleSide::QueenSide] {
+                assert!(!game.has_castling_right(color, side));
+                assert!(!game.can_castle(color, side));
+
+                game.set_castling(color, side, true);
+                assert!(game.has_castling_right(color, side));
+                assert!(game.can_castle(color, side));
+            }
+        }
+        assert_eq!(game.fen(), "r3k2r/8/8/8/8/8/8/R3K2R w KQkq -");
+
+        game.set_castling(Color::White, CastleSide::QueenSide, false);
+        game.set_castling(Color::Black, CastleSide::KingSide, false);
+        assert_eq!(game.fen(), "r3k2r/8/8/8/8/8/8/R3K2R w Kq -");
+        assert!(game
+            .castling_rights()
+            .get(Color::White, CastleSide::KingSide));
+        assert!(!game
+            .castling_rights()
+            .get(Color::Black, CastleSide::KingSide));
+    }
+
+    #[test]
+    pub fn generated_moves_skip_illegal_castling() {
+        let castles = |fen: &str| {
+            let mut castles = Game::from_fen(fen)
+                .unwrap()
+                .gen_all_moves()
+                .unwrap()
+                .into_iter()
+                .filter(|m| m.is_castle())
+                .map(|m| m.to())
+                .collect::<Vec<_>>();
+            castles.sort();
+            castles
+        };
+
+        assert_eq!(
+            castles("4k3/8/8/8/8/8/8/R3K2R w KQ -"),
+            vec![(2, 0), (6, 0)]
+        );
+        // Out of check, the rook on e8 checks the king
+        assert_eq!(castles("4r1k1/8/8/8/8/8/8/R3K2R w KQ -"), vec![]);
+        // Through check, the bishop on b5 attacks the empty f1
+        assert_eq!(castles("4k3/8/8/1b6/8/8/8/R3K2R w KQ -"), vec![(2, 0)]);
+        // Into check, the knight on h3 attacks g1
+        assert_eq!(castles("4k3/8/8/8/8/7n/8/R3K2R w KQ -"), vec![(2, 0)]);
+    }
+}
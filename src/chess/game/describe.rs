@@ -0,0 +1,110 @@
+use alloc::{format, string::String, vec};
+
+use crate::{CastleSide, Color, Game};
+
+impl Game {
+    /// Returns a short sentence describing the state of the game
+    ///
+    /// A finished game is described by how it ended, e.g `Checkmate, Black wins`. Otherwise the
+    /// sentence says whose turn it is, which way the side to move can castle right now and if it
+    /// is in check. Repetitions aren't known by the game, see [`Game::is_draw`].
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// assert_eq!(Game::start_pos().describe(), "White to move");
+    ///
+    /// let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b k -").unwrap();
+    /// assert_eq!(game.describe(), "Black to move, Black can castle kingside");
+    /// ```
+    pub fn describe(&self) -> String {
+        let turn = color_name(self.turn);
+
+        if self.is_checkmate() {
+            return format!("Checkmate, {} wins", color_name(self.turn.opposite()));
+        }
+        if self.is_stalemate() {
+            return String::from("Stalemate, draw");
+        }
+        if self.is_fifty_move_rule() {
+            return String::from("Draw by the fifty move rule");
+        }
+        if self.is_insufficient_material() {
+            return String::from("Draw by insufficient material");
+        }
+        if self.is_dead_position() {
+            return String::from("Draw by dead position");
+        }
+
+        let mut parts = vec![format!("{} to move", turn)];
+
+        let castle = match (
+            self.can_castle(self.turn, CastleSide::KingSide),
+            self.can_castle(self.turn, CastleSide::QueenSide),
+        ) {
+            (true, true) => Some("kingside and queenside"),
+            (true, false) => Some("kingside"),
+            (false, true) => Some("queenside"),
+            (false, false) => None,
+        };
+        if let Some(castle) = castle {
+            parts.push(format!("{} can castle {}", turn, castle));
+        }
+
+        if self.is_check() {
+            parts.push(String::from("in check"));
+        }
+
+        parts.join(", ")
+    }
+}
+
+/// Internal helper that returns the name of a color starting with a capital letter
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "White",
+        Color::Black => "Black",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn describe_known_positions() {
+        let describe = |fen: &str| Game::from_fen(fen).unwrap().describe();
+
+        assert_eq!(
+            describe("r3k2r/8/8/8/8/8/8/R3K2R w KQkq -"),
+            "White to move, White can castle kingside and queenside"
+        );
+        // The rook on f8 stops castling kingside
+        assert_eq!(
+            describe("4kr2/8/8/8/8/8/8/R3K2R w KQ -"),
+            "White to move, White can castle queenside"
+        );
+        assert_eq!(
+            describe("4k3/8/8/8/8/8/8/4R1K1 b - -"),
+            "Black to move, in check"
+        );
+        assert_eq!(
+            describe("R5k1/5ppp/8/8/8/8/8/6K1 b - -"),
+            "Checkmate, White wins"
+        );
+        assert_eq!(describe("k7/8/1Q6/8/8/8/8/7K b - -"), "Stalemate, draw");
+        assert_eq!(
+            describe("4k3/8/8/8/8/8/8/4KN2 w - -"),
+            "Draw by insufficient material"
+        );
+        assert_eq!(
+            describe("4k3/8/8/p1p1p1p1/P1P1P1P1/8/8/4K3 w - -"),
+            "Draw by dead position"
+        );
+        assert_eq!(
+            describe("4k3/8/8/8/8/8/4P3/R3K3 w - - 100 80"),
+            "Draw by the fifty move rule"
+        );
+    }
+}
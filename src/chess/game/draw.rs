@@ -0,0 +1,506 @@
+use alloc::vec::Vec;
+
+use crate::{error::FromFenError, Color, Game, PieceType};
+
+use super::attack_tables::{self, KING_ATTACKS, PAWN_ATTACKS};
+
+/// The tiles where `x + y` is even, the dark tiles a1, c1 and so on
+const EVEN_TILES: u64 = 0xAA55_AA55_AA55_AA55;
+
+/// The positions that have occurred earlier in a game, used to detect repetitions
+///
+/// Positions are stored by their Polyglot key, so only the position, turn, castling rights and
+/// capturable en passant tile matter, which are the same things the repetition rule cares about.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Game, GameHistory};
+///
+/// let mut game = Game::start_pos();
+/// let mut history = GameHistory::new();
+///
+/// for san in ["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1", "Ng8"] {
+///     history.push(&game);
+///     game.apply_move(game.parse_san(san).unwrap()).unwrap();
+/// }
+///
+/// assert!(game.is_threefold_repetition(&history));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameHistory {
+    keys: Vec<u64>,
+}
+
+impl GameHistory {
+    /// Creates an empty history
+    pub fn new() -> GameHistory {
+        GameHistory::default()
+    }
+
+    /// Creates a history from the FEN strings of the earlier positions of a game
+    ///
+    /// # Arguments
+    /// * `fens` - The earlier positions, in any order since only the count of each one matters
+    ///
+    /// # Returns
+    /// * `Result<GameHistory, FromFenError>` - The history or the error of the first invalid FEN
+    pub fn from_fens(fens: &[&str]) -> Result<GameHistory, FromFenError> {
+        let mut history = GameHistory::new();
+
+        for fen in fens {
+            history.push(&Game::from_fen(fen)?);
+        }
+
+        Ok(history)
+    }
+
+    /// Adds a position to the history, call this with the game before every move is applied
+    pub fn push(&mut self, game: &Game) {
+        self.keys.push(game.polyglot_key());
+    }
+
+    /// Removes the last position from the history, used when a move is taken back
+    pub fn pop(&mut self) {
+        self.keys.pop();
+    }
+
+    /// Returns how many times a position occurs in the history
+    pub fn count(&self, game: &Game) -> usize {
+        let key = game.polyglot_key();
+        self.keys.iter().filter(|k| **k == key).count()
+    }
+}
+
+/// How a finished game ended for one of the colors, see [`Game::result_for`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Game {
+    /// Returns how the game ended from the view of a color
+    ///
+    /// Only the current position is known, so draws by repetition aren't detected, see
+    /// [`Game::is_draw`] for that.
+    ///
+    /// # Arguments
+    /// * `color` - The color to get the outcome for
+    ///
+    /// # Returns
+    /// * `Option<Outcome>` - The outcome for `color`, or None if the game isn't finished
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Game, Outcome};
+    ///
+    /// let game = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - -").unwrap();
+    ///
+    /// assert_eq!(game.result_for(Color::White), Some(Outcome::Win));
+    /// assert_eq!(game.result_for(Color::Black), Some(Outcome::Loss));
+    /// assert_eq!(Game::start_pos().result_for(Color::White), None);
+    /// ```
+    pub fn result_for(&self, color: Color) -> Option<Outcome> {
+        // A checkmate on the last move before the fifty move rule still wins
+        if let Some(winner) = self.winner() {
+            return Some(if winner == color {
+                Outcome::Win
+            } else {
+                Outcome::Loss
+            });
+        }
+
+        if self.is_draw(&GameHistory::new()) {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
+    }
+
+    /// Returns if the game is drawn
+    ///
+    /// The game is drawn by stalemate, the fifty move rule, threefold repetition or a dead
+    /// position, see [`Game::is_dead_position`]. A checkmate is never a draw, even if the fifty
+    /// move rule applies.
+    ///
+    /// # Arguments
+    /// * `history` - The earlier positions of the game, pass an empty history if they aren't
+    ///   tracked, then repetitions are never detected
+    pub fn is_draw(&self, history: &GameHistory) -> bool {
+        !self.is_checkmate()
+            && (self.is_fifty_move_rule()
+                || self.is_dead_position()
+                || self.is_threefold_repetition(history)
+                || self.is_stalemate())
+    }
+
+    /// Returns if fifty moves by each side have been made without a capture or pawn move
+    pub fn is_fifty_move_rule(&self) -> bool {
+        self.halfmove_clock >= 100
+    }
+
+    /// Returns if the current position has occurred at least three times
+    ///
+    /// # Arguments
+    /// * `history` - The earlier positions of the game, not including the current one
+    pub fn is_threefold_repetition(&self, history: &GameHistory) -> bool {
+        history.count(self) + 1 >= 3
+    }
+
+    /// Returns if the current position has occurred at least three times, given the earlier
+    /// positions as FEN strings
+    ///
+    /// Positions are compared like in [`GameHistory`], so the move counters of the FENs don't
+    /// matter.
+    ///
+    /// # Arguments
+    /// * `prior_fens` - The earlier positions of the game, not including the current one
+    ///
+    /// # Returns
+    /// * `Result<bool, FromFenError>` - If the position is repeated, or the error of the first
+    ///   invalid FEN
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::start_pos();
+    /// let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -";
+    ///
+    /// assert!(!game.is_threefold_with_history(&[start]).unwrap());
+    /// assert!(game.is_threefold_with_history(&[start, start]).unwrap());
+    /// ```
+    pub fn is_threefold_with_history(&self, prior_fens: &[&str]) -> Result<bool, FromFenError> {
+        Ok(self.is_threefold_repetition(&GameHistory::from_fens(prior_fens)?))
+    }
+
+    /// Returns if neither side has enough material to checkmate
+    ///
+    /// This is the case for king against king, king and a minor piece against king and when all
+    /// remaining minor pieces are bishops on tiles of the same color.
+    pub fn is_insufficient_material(&self) -> bool {
+        let pieces = |piece_type| {
+            self.board.pieces(piece_type, Color::White)
+                | self.board.pieces(piece_type, Color::Black)
+        };
+
+        if pieces(PieceType::Pawn) | pieces(PieceType::Rook) | pieces(PieceType::Queen) != 0 {
+            return false;
+        }
+
+        let knights = pieces(PieceType::Knight);
+        let bishops = pieces(PieceType::Bishop);
+
+        if (knights | bishops).count_ones() <= 1 {
+            return true;
+        }
+
+        // Bishops that all stand on light or all on dark tiles can never give mate
+        knights == 0 && (bishops & EVEN_TILES == 0 || bishops & !EVEN_TILES == 0)
+    }
+
+    /// Returns if no sequence of legal moves can end in checkmate
+    ///
+    /// Besides [`Game::is_insufficient_material`] this finds positions with only kings, pawns and
+    /// bishops on tiles of one color, where every pawn is blocked by an enemy pawn and can never
+    /// capture, and the kings can't reach an undefended pawn or each other. The test is
+    /// conservative: a position it returns true for is always dead, but some dead positions aren't
+    /// found.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// // The pawns are locked and the kings can never get through
+    /// let game = Game::from_fen("4k3/8/8/p1p1p1p1/P1P1P1P1/8/8/4K3 w - -").unwrap();
+    /// assert!(game.is_dead_position());
+    /// assert!(!game.is_insufficient_material());
+    /// ```
+    pub fn is_dead_position(&self) -> bool {
+        self.is_insufficient_material() || self.is_locked_position()
+    }
+
+    /// Internal helper that finds dead positions where the pawns are locked, see
+    /// [`Game::is_dead_position`]
+    fn is_locked_position(&self) -> bool {
+        // An en passant capture would open the position
+        if self.en_passant.is_some() {
+            return false;
+        }
+
+        let board = &self.board;
+        let both = |piece_type| {
+            board.pieces(piece_type, Color::White) | board.pieces(piece_type, Color::Black)
+        };
+
+        if both(PieceType::Knight) | both(PieceType::Rook) | both(PieceType::Queen) != 0 {
+            return false;
+        }
+
+        let pawns = both(PieceType::Pawn);
+        let bishops = both(PieceType::Bishop);
+        let bishop_tiles = if bishops & EVEN_TILES != 0 {
+            EVEN_TILES
+        } else {
+            !EVEN_TILES
+        };
+
+        if bishops & !bishop_tiles != 0 {
+            return false;
+        }
+
+        let mut pawn_attacks = [0; 2];
+
+        for color in [Color::White, Color::Black] {
+            let own_pawns = board.pieces(PieceType::Pawn, color);
+            let in_front = match color {
+                Color::White => own_pawns << 8,
+                Color::Black => own_pawns >> 8,
+            };
+
+            if in_front & !board.pieces(PieceType::Pawn, color.opposite()) != 0 {
+                return false;
+            }
+
+            pawn_attacks[color as usize] = attack_tables::tiles(own_pawns)
+                .map(|(x, y)| PAWN_ATTACKS[color as usize][y * 8 + x])
+                .fold(0, |acc, attacks| acc | attacks);
+
+            if pawn_attacks[color as usize] & board.pieces(PieceType::Pawn, color.opposite()) != 0 {
+                return false;
+            }
+
+            // A pawn could take an enemy bishop on a free tile, or be taken by one
+            if board.pieces(PieceType::Bishop, color.opposite()) != 0
+                && (pawn_attacks[color as usize] & bishop_tiles & !pawns != 0
+                    || own_pawns & bishop_tiles != 0)
+            {
+                return false;
+            }
+        }
+
+        let mut regions = [0; 2];
+
+        for color in [Color::White, Color::Black] {
+            let enemy_attacks = pawn_attacks[color.opposite() as usize];
+            let free = !pawns & !enemy_attacks;
+            let Some((x, y)) = board.get_king_pos(color) else {
+                return false;
+            };
+
+            let mut region = 1 << (y * 8 + x);
+            if region & free == 0 {
+                return false;
+            }
+
+            // Every tile the king can ever walk to
+            loop {
+                let next = (region | king_moves(region)) & free;
+                if next == region {
+                    break;
+                }
+                region = next;
+            }
+
+            let undefended = board.pieces(PieceType::Pawn, color.opposite()) & !enemy_attacks;
+            if king_moves(region) & undefended != 0 {
+                return false;
+            }
+
+            regions[color as usize] = region;
+        }
+
+        if (regions[0] | king_moves(regions[0])) & regions[1] != 0 {
+            return false;
+        }
+
+        // A bishop can only check on its own tile color, the king can then always step to a tile
+        // of the other color next to it
+        for color in [Color::White, Color::Black] {
+            if board.pieces(PieceType::Bishop, color.opposite()) == 0 {
+                continue;
+            }
+
+            let region = regions[color as usize];
+
+            for (x, y) in attack_tables::tiles(region & bishop_tiles) {
+                if KING_ATTACKS[y * 8 + x] & !bishop_tiles & region == 0 {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Internal helper that returns the tiles a king can step to from any of the tiles in a mask
+fn king_moves(mask: u64) -> u64 {
+    attack_tables::tiles(mask)
+        .map(|(x, y)| KING_ATTACKS[y * 8 + x])
+        .fold(0, |acc, attacks| acc | attacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn result_for_both_colors() {
+        // White mated with the rook on the back rank
+        let game = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - -").unwrap();
+        assert_eq!(game.result_for(Color::White), Some(Outcome::Win));
+        assert_eq!(game.result_for(Color::Black), Some(Outcome::Loss));
+
+        // Mate on the move that reaches the fifty move rule
+        let game = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80").unwrap();
+        assert_eq!(game.result_for(Color::White), Some(Outcome::Win));
+
+        let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - -").unwrap();
+        assert_eq!(game.result_for(Color::White), Some(Outcome::Draw));
+        assert_eq!(game.result_for(Color::Black), Some(Outcome::Draw));
+
+        assert_eq!(Game::start_pos().result_for(Color::Black), None);
+    }
+
+    #[test]
+    pub fn draw_by_stalemate() {
+        let game = Game::from_fen("k7/8/1Q6/8/8/8/8/7K b - -").unwrap();
+
+        assert!(game.is_stalemate());
+        assert!(game.is_draw(&GameHistory::new()));
+        assert!(!Game::start_pos().is_draw(&GameHistory::new()));
+    }
+
+    #[test]
+    pub fn draw_by_fifty_move_rule() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 99 80").unwrap();
+        assert!(!game.is_draw(&GameHistory::new()));
+
+        let mut after_rook_move = game;
+        after_rook_move
+            .apply_move(game.parse_san("Ra2").unwrap())
+            .unwrap();
+        assert!(after_rook_move.is_fifty_move_rule());
+        assert!(after_rook_move.is_draw(&GameHistory::new()));
+
+        let mut after_pawn_move = game;
+        after_pawn_move
+            .apply_move(game.parse_san("e3").unwrap())
+            .unwrap();
+        assert!(!after_pawn_move.is_draw(&GameHistory::new()));
+    }
+
+    #[test]
+    pub fn checkmate_is_not_a_draw_by_fifty_move_rule() {
+        let game = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80").unwrap();
+
+        assert!(game.is_fifty_move_rule());
+        assert!(game.is_checkmate());
+        assert!(!game.is_draw(&GameHistory::new()));
+    }
+
+    #[test]
+    pub fn draw_by_threefold_repetition() {
+        let mut game = Game::start_pos();
+        let mut history = GameHistory::new();
+
+        for (i, san) in ["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1", "Ng8"]
+            .iter()
+            .enumerate()
+        {
+            assert!(!game.is_draw(&history), "Drawn after {} moves", i);
+
+            history.push(&game);
+            game.apply_move(game.parse_san(san).unwrap()).unwrap();
+        }
+
+        assert_eq!(history.count(&game), 2);
+        assert!(game.is_draw(&history));
+        assert!(!game.is_draw(&GameHistory::new()));
+    }
+
+    #[test]
+    pub fn draw_by_insufficient_material() {
+        let history = GameHistory::new();
+
+        for fen in [
+            "4k3/8/8/8/8/8/8/4K3 w - -",
+            "4k3/8/8/8/8/8/8/4KN2 w - -",
+            "4kb2/8/8/8/8/8/8/4K3 w - -",
+            // Both bishops on light tiles
+            "2b1k3/8/8/8/8/8/8/4KB2 w - -",
+        ] {
+            assert!(Game::from_fen(fen).unwrap().is_draw(&history), "{}", fen);
+        }
+
+        for fen in [
+            "4k3/8/8/8/8/8/4P3/4K3 w - -",
+            "4k3/8/8/8/8/8/8/3NKN2 w - -",
+            // Bishops on different colors
+            "3bk3/8/8/8/8/8/8/4KB2 w - -",
+            "4k3/8/8/8/8/8/8/4KR2 w - -",
+        ] {
+            assert!(!Game::from_fen(fen).unwrap().is_draw(&history), "{}", fen);
+        }
+    }
+
+    #[test]
+    pub fn dead_positions_with_locked_pawns() {
+        for fen in [
+            "4k3/8/8/p1p1p1p1/P1P1P1P1/8/8/4K3 w - -",
+            // A locked chain with a bishop that can never attack the black pawns
+            "4k3/8/1p1p1p1p/pPpPpPpP/P1P1P1P1/8/2B5/4K3 w - -",
+            // No locked pawns, only insufficient material
+            "4k3/8/8/8/8/8/8/4KN2 w - -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            assert!(game.is_dead_position(), "{}", fen);
+            assert!(game.is_draw(&GameHistory::new()), "{}", fen);
+        }
+
+        for fen in [
+            "4k3/8/8/8/8/8/4P3/4K3 w - -",
+            // The pawns can take each other
+            "4k3/8/8/pppppppp/PPPPPPPP/8/8/4K3 w - -",
+            // The kings can walk through the open files
+            "4k3/8/8/p1p1p3/P1P1P3/8/8/4K3 w - -",
+            // The bishops stand on tiles of different colors
+            "4k3/8/1p1p1p1p/pPpPpPpP/P1P1P1P1/8/2BB4/4K3 w - -",
+            // The bishop can take the pawns on its tile color
+            "4k3/8/8/p1p1p1p1/P1P1P1P1/8/3B4/4K3 w - -",
+            "4k3/8/8/p1p1p1p1/P1P1P1P1/8/8/4KN2 w - -",
+        ] {
+            assert!(!Game::from_fen(fen).unwrap().is_dead_position(), "{}", fen);
+        }
+    }
+
+    #[test]
+    pub fn threefold_repetition_from_fens() {
+        let game = Game::from_fen("6k1/8/8/8/8/8/8/R5K1 b - - 8 30").unwrap();
+
+        // The move counters don't matter, only the position
+        let fens = [
+            "6k1/8/8/8/8/8/8/R5K1 b - - 4 28",
+            "6k1/8/8/8/8/8/8/R6K w - - 5 29",
+            "7k/8/8/8/8/8/8/R6K b - - 5 29",
+            "6k1/8/8/8/8/8/8/R5K1 b - - 6 29",
+        ];
+        assert!(game.is_threefold_with_history(&fens).unwrap());
+        assert!(!game.is_threefold_with_history(&fens[..3]).unwrap());
+
+        // The same position with white to move isn't a repetition
+        assert!(!game
+            .is_threefold_with_history(&[
+                "6k1/8/8/8/8/8/8/R5K1 w - - 4 28",
+                "6k1/8/8/8/8/8/8/R5K1 w - - 6 29",
+            ])
+            .unwrap());
+
+        assert!(matches!(
+            game.is_threefold_with_history(&["not a fen at all"]),
+            Err(FromFenError::IncorrectAmountOfParts)
+        ));
+    }
+}
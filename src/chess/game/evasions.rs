@@ -0,0 +1,182 @@
+use alloc::vec::Vec;
+
+use crate::{Game, Move, MoveList};
+
+use super::attack_tables::{self, KING_ATTACKS};
+
+impl Game {
+    /// Returns the moves that get the current turn out of check
+    ///
+    /// When in check these are all the legal moves, the same as [`Game::gen_all_moves`] returns,
+    /// but only king moves, captures of the checker and blocks are looked at.
+    ///
+    /// # Returns
+    /// * `Vec<Move>` - The moves out of check, empty if the current turn isn't in check
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// // The king can step aside, and the bishop can block on e2
+    /// let game = Game::from_fen("4k3/8/8/1b6/8/8/8/4R1K1 b - -").unwrap();
+    /// assert_eq!(game.evasion_moves().len(), 5);
+    ///
+    /// assert!(Game::start_pos().evasion_moves().is_empty());
+    /// ```
+    pub fn evasion_moves(&self) -> Vec<Move> {
+        let evasions = self.evasion_mask();
+        if evasions == !0 {
+            return Vec::new();
+        }
+
+        let mut moves = MoveList::new();
+        self.gen_evasions_into(evasions, &mut moves);
+
+        moves.to_vec()
+    }
+
+    /// Internal helper that adds all legal moves to a move list when the current turn is in check
+    ///
+    /// Only the moves that can get out of check are generated. The king moves to tiles that
+    /// aren't attacked, and in a single check the other pieces capture the checker or block the
+    /// line between the checker and the king. In a double check only the king can move.
+    ///
+    /// # Arguments
+    /// * `evasions` - The mask from [`Game::evasion_mask`]
+    pub(super) fn gen_evasions_into(&self, evasions: u64, moves: &mut MoveList) {
+        let (king_x, king_y) = self
+            .board
+            .get_king_pos(self.turn)
+            .expect("Only a king can be in check");
+        let king = king_y * 8 + king_x;
+
+        // The king can't hide behind itself from a slider, so it is removed before looking for
+        // attackers of the tiles it can move to
+        let occupied = self.board.occupied() & !(1 << king);
+        let targets = KING_ATTACKS[king] & !self.board.color_pieces(self.turn);
+
+        for (to_x, to_y) in attack_tables::tiles(targets) {
+            if self.attackers_mask_with(to_y * 8 + to_x, self.turn.opposite(), occupied) != 0 {
+                continue;
+            }
+
+            let from = (king_x, king_y);
+            let to = (to_x, to_y);
+
+            moves.push(match self.board.get_tile(to_x, to_y) {
+                Some(_) => Move::Capture {
+                    from,
+                    to,
+                    capture: to,
+                },
+                None => Move::Quiet { from, to },
+            });
+        }
+
+        // Double check
+        if evasions == 0 {
+            return;
+        }
+
+        for (x, y) in attack_tables::tiles(self.board.color_pieces(self.turn) & !(1 << king)) {
+            self.gen_legal_moves_into(x, y, evasions, moves);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Brute force reference, every pseudo legal move that doesn't leave the king in check
+    fn reference(game: &Game) -> HashSet<Move> {
+        let mut moves = MoveList::new();
+
+        for y in 0..8 {
+            for x in 0..8 {
+                if game.board.is_occupied_by(x, y, game.turn) {
+                    game.gen_pseudo_legal_moves(x, y, false, &mut moves);
+                }
+            }
+        }
+
+        moves
+            .into_iter()
+            .filter(|m| {
+                let mut game = *game;
+                game.apply_move(*m).unwrap();
+                !game.can_capture_king(game.turn)
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn evasions_match_brute_force() {
+        for fen in [
+            // Single check by a rook, can be blocked or captured
+            "4k3/8/8/8/1b6/8/r7/4R1K1 b - -",
+            // Single check by a knight, can only be captured
+            "4k3/8/3N4/8/8/8/1B6/6K1 b - -",
+            // Double check by a rook and a bishop
+            "4k3/8/8/1B6/8/8/8/4R1K1 b - -",
+            // The pawn giving check can be captured en passant
+            "8/8/8/2k5/3Pp3/8/8/4K3 b - d3",
+            // The king can't step back along the line of the checking slider
+            "4k3/8/8/8/8/8/8/r3K3 w - -",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            let moves = game.gen_all_moves().unwrap_or_default();
+
+            assert_eq!(moves.len(), reference(&game).len(), "{}", fen);
+            assert_eq!(
+                moves.iter().copied().collect::<HashSet<_>>(),
+                reference(&game)
+            );
+
+            if game.is_check() {
+                assert_eq!(game.evasion_moves(), moves, "{}", fen);
+            } else {
+                assert!(game.evasion_moves().is_empty(), "{}", fen);
+            }
+        }
+    }
+
+    #[test]
+    pub fn evasions_match_brute_force_in_all_checks_of_a_tree() {
+        let mut checks = 0;
+
+        let mut positions = vec![Game::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+        )
+        .unwrap()];
+
+        for _ in 0..2 {
+            positions = positions
+                .iter()
+                .flat_map(|game| {
+                    game.legal_moves_iter().map(move |m| {
+                        let mut game = *game;
+                        game.apply_move(m).unwrap();
+                        game
+                    })
+                })
+                .collect();
+
+            for game in positions.iter().filter(|game| game.is_check()) {
+                checks += 1;
+                let moves = game.legal_moves_iter().collect::<HashSet<_>>();
+                assert_eq!(moves, reference(game), "{}", game.fen());
+                assert_eq!(
+                    game.evasion_moves().into_iter().collect::<HashSet<_>>(),
+                    moves,
+                    "{}",
+                    game.fen()
+                );
+            }
+        }
+
+        assert!(checks > 0);
+    }
+}
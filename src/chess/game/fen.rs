@@ -1,16 +1,26 @@
-use std::collections::HashSet;
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
 
-use crate::{error::FromFenError, Board, Color, Game, PieceType};
+use crate::{
+    coord, error::FromFenError, Board, CastleSide, CastlingRights, Color, Game, Piece, PieceType,
+};
 
 impl Game {
     /// Creates a new game from a FEN string
     ///
+    /// The half move clock and full move number can be left out, they then default to 0 and 1.
+    /// The parts can be separated by any amount of whitespace. The turn is parsed leniently, `W`
+    /// and `B` are accepted as well as `w` and `b`.
+    ///
+    /// The en passant tile is accepted as long as the pawn that just moved two tiles stands in
+    /// front of it, even if no pawn can capture it. Use [`Game::canonical_fen`] to only keep en
+    /// passant tiles where a capture is possible.
+    ///
     /// # Arguments
     /// * `fen` - A string that holds the FEN string
     ///
     /// # Returns
     /// * `Result<game, FromFenError>` - A result that holds the game if the fen string is valid
-    /// or an error if the FEN string is invalid
+    ///   or an error if the FEN string is invalid
     ///
     /// # Examples
     /// ```
@@ -20,20 +30,41 @@ impl Game {
     /// let game = Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -");
     /// ```
     pub fn from_fen(fen: &str) -> Result<Game, FromFenError> {
-        let fen_parts = fen.split(' ').collect::<Vec<&str>>();
+        // Some programs write extra spaces between or around the parts
+        let fen_parts = fen.split_whitespace().collect::<Vec<&str>>();
+
+        // The half move clock and full move number are optional
+        if fen_parts.len() != 4 && fen_parts.len() != 6 {
+            return Err(FromFenError::IncorrectAmountOfParts);
+        }
 
         let fen_part_pieces = fen_parts[0];
         let fen_part_turn = fen_parts[1];
         let fen_part_castling = fen_parts[2];
         let fen_part_en_passant = fen_parts[3];
 
-        if fen_parts.len() != 4 {
-            return Err(FromFenError::IncorrectAmountOfParts);
-        }
+        let (halfmove_clock, fullmove_number) = if fen_parts.len() == 6 {
+            let halfmove_clock = fen_parts[4]
+                .parse::<u32>()
+                .map_err(|_| FromFenError::InvalidMoveCounter)?;
+            let fullmove_number = fen_parts[5]
+                .parse::<u32>()
+                .map_err(|_| FromFenError::InvalidMoveCounter)?;
+
+            (halfmove_clock, fullmove_number)
+        } else {
+            (0, 1)
+        };
 
         let board = Board::from_fen(fen_part_pieces)?;
 
-        let turn = match fen_part_turn {
+        // Some programs write the turn in uppercase or with extra characters like `w.`, so the
+        // turn is accepted in any case and anything that isn't a letter is ignored
+        let turn = match fen_part_turn
+            .trim_matches(|c: char| !c.is_ascii_alphabetic())
+            .to_ascii_lowercase()
+            .as_str()
+        {
             "w" => Color::White,
             "b" => Color::Black,
             _ => return Err(FromFenError::UnknownTurn),
@@ -43,45 +74,90 @@ impl Game {
 
         let en_passant = en_passant(fen_part_en_passant)?;
 
-        let en_passant = if let Some((ep_x, ep_y)) = en_passant {
-            // Because i store en passant as the tile of the pawn that can be captured,
-            let ep_y = if turn == Color::White {
-                ep_y + 1
-            } else {
-                ep_y - 1
-            };
-
-            let ocp_piece = board.get_tile(ep_x, ep_y);
-
-            if let Some(piece) = ocp_piece {
-                if piece.piece_type != PieceType::Pawn || piece.color == turn {
-                    return Err(FromFenError::InvalidEnPassant);
-                }
-            } else {
-                return Err(FromFenError::InvalidEnPassant);
+        // Because i store en passant as the tile of the pawn that can be captured,
+        let en_passant = match en_passant {
+            Some(tile) => {
+                Some(en_passant_pawn(&board, turn, tile).ok_or(FromFenError::InvalidEnPassant)?)
             }
-
-            Some((ep_x, ep_y))
-        } else {
-            None
+            None => None,
         };
 
         Ok(Game {
             board,
             turn,
             en_passant,
-            white_kingside_castle: castling[0],
-            white_queenside_castle: castling[1],
-            black_kingside_castle: castling[2],
-            black_queenside_castle: castling[3],
+            castling_rights: CastlingRights {
+                white_kingside: castling[0],
+                white_queenside: castling[1],
+                black_kingside: castling[2],
+                black_queenside: castling[3],
+            },
+            halfmove_clock,
+            fullmove_number,
         })
     }
 
+    /// Creates a new game from a FEN string, keeping an en passant tile no pawn can capture
+    ///
+    /// The same as [`Game::from_fen`], which already stores the en passant tile without checking
+    /// that a capture is possible. The name is for code that reads FEN strings from programs that
+    /// always write the tile after a double pawn push.
+    ///
+    /// # Arguments
+    /// * `fen` - A string that holds the FEN string
+    ///
+    /// # Returns
+    /// * `Result<game, FromFenError>` - The game or an error if the FEN string is invalid
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// // No black pawn can capture on e3
+    /// let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3";
+    /// let game = Game::from_fen_lenient_ep(fen).unwrap();
+    ///
+    /// assert_eq!(game.fen(), fen);
+    /// ```
+    pub fn from_fen_lenient_ep(fen: &str) -> Result<Game, FromFenError> {
+        Game::from_fen(fen)
+    }
+
     /// Returns the game as a FEN string
     ///
+    /// The move counters are left out, use [`Game::fen_with_options`] to include them or to write
+    /// the castling rights in another notation.
+    ///
     /// # Returns
     /// * `String` - The game as a FEN string
     pub fn fen(&self) -> String {
+        self.fen_with_options(FenOptions::default())
+    }
+
+    /// Returns the game as a FEN string written with certain options
+    ///
+    /// # Arguments
+    /// * `options` - How to write the castling rights and if the move counters are included
+    ///
+    /// # Returns
+    /// * `String` - The game as a FEN string
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{CastlingNotation, FenOptions, Game};
+    ///
+    /// let game = Game::start_pos();
+    /// let options = FenOptions {
+    ///     castling: CastlingNotation::Shredder,
+    ///     move_counters: true,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     game.fen_with_options(options),
+    ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1"
+    /// );
+    /// ```
+    pub fn fen_with_options(&self, options: FenOptions) -> String {
         let board = self.board.fen();
 
         let turn = match self.turn {
@@ -89,114 +165,297 @@ impl Game {
             Color::Black => "b",
         };
 
-        macro_rules! castling {
-            ($x:expr, $y:expr) => {
-                if ($x) {
-                    $y
-                } else {
-                    ""
-                }
+        let castling = self.castling_fen(options.castling);
+
+        let en_passant = if let Some((ep_x, ep_y)) = self.en_passant {
+            let ep_y = if self.turn == Color::White {
+                ep_y + 1
+            } else {
+                ep_y - 1
             };
+
+            coord::to_algebraic((ep_x, ep_y))
+        } else {
+            "-".to_string()
+        };
+
+        if options.move_counters {
+            format!(
+                "{} {} {} {} {} {}",
+                board, turn, castling, en_passant, self.halfmove_clock, self.fullmove_number
+            )
+        } else {
+            format!("{} {} {} {}", board, turn, castling, en_passant)
         }
+    }
 
-        let castling = format!(
-            "{}{}{}{}",
-            castling!(self.white_kingside_castle, "K"),
-            castling!(self.white_queenside_castle, "Q"),
-            castling!(self.black_kingside_castle, "k"),
-            castling!(self.black_queenside_castle, "q")
-        );
-        let castling = if castling.is_empty() {
+    /// Internal helper that writes the castling part of the FEN string
+    fn castling_fen(&self, notation: CastlingNotation) -> String {
+        // The castling rook always starts in the corner, on the h file for the king side
+        let (king_side, queen_side) = match notation {
+            CastlingNotation::Standard | CastlingNotation::XFen => ('K', 'Q'),
+            CastlingNotation::Shredder => ('H', 'A'),
+        };
+
+        let mut castling = String::new();
+
+        for color in [Color::White, Color::Black] {
+            for (side, c) in [
+                (CastleSide::KingSide, king_side),
+                (CastleSide::QueenSide, queen_side),
+            ] {
+                if self.castling_rights.get(color, side) {
+                    castling.push(match color {
+                        Color::White => c,
+                        Color::Black => c.to_ascii_lowercase(),
+                    });
+                }
+            }
+        }
+
+        if castling.is_empty() {
             "-".to_string()
         } else {
             castling
+        }
+    }
+
+    /// Returns the game as a FEN string where the en passant tile only is set if the pawn
+    /// actually can be captured
+    ///
+    /// A FEN string lists the en passant tile after every double pawn push, even when no pawn
+    /// can capture it. Two such games are the same position, so this is the FEN string to use
+    /// when comparing positions.
+    ///
+    /// # Returns
+    /// * `String` - The game as a normalized FEN string
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3").unwrap();
+    /// assert_eq!(game.canonical_fen(), "4k3/8/8/8/4P3/8/8/4K3 b - -");
+    /// ```
+    pub fn canonical_fen(&self) -> String {
+        let mut game = *self;
+
+        if let Some(ep) = self.en_passant {
+            let can_capture = self
+                .gen_all_moves()
+                .unwrap_or_default()
+                .iter()
+                .any(|m| m.capture() == Some(ep) && m.to() != ep);
+
+            if !can_capture {
+                game.en_passant = None;
+            }
+        }
+
+        game.fen()
+    }
+
+    /// Returns the differences between two games in a human readable form
+    ///
+    /// This is meant to make failing comparisons of games easy to debug.
+    ///
+    /// # Arguments
+    /// * `other` - The game to compare to
+    ///
+    /// # Returns
+    /// * `Vec<String>` - One line for every difference, written as `self vs other`. Empty if the
+    ///   games are the same
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let a = Game::from_fen("4k3/8/8/8/4P3/8/8/4K3 w K -").unwrap();
+    /// let b = Game::from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+    ///
+    /// assert_eq!(
+    ///     a.fen_diff(&b),
+    ///     vec!["turn: white vs black", "e4: white pawn vs empty", "castling K lost"]
+    /// );
+    /// ```
+    pub fn fen_diff(&self, other: &Game) -> Vec<String> {
+        let mut diff = vec![];
+
+        let color_name = |color: Color| match color {
+            Color::White => "white",
+            Color::Black => "black",
         };
 
-        let en_passant = if let Some((ep_x, ep_y)) = self.en_passant {
-            let ep_y = if self.turn == Color::White {
-                ep_y - 1
-            } else {
-                ep_y + 1
+        if self.turn != other.turn {
+            diff.push(format!(
+                "turn: {} vs {}",
+                color_name(self.turn),
+                color_name(other.turn)
+            ));
+        }
+
+        let tile_name = |tile: Option<Piece>| match tile {
+            Some(piece) => {
+                let piece_type = match piece.piece_type {
+                    PieceType::Pawn => "pawn",
+                    PieceType::Knight => "knight",
+                    PieceType::Bishop => "bishop",
+                    PieceType::Rook => "rook",
+                    PieceType::Queen => "queen",
+                    PieceType::King => "king",
+                };
+
+                format!("{} {}", color_name(piece.color), piece_type)
+            }
+            None => "empty".to_string(),
+        };
+
+        for (i, (a, b)) in self.board.tiles.iter().zip(other.board.tiles).enumerate() {
+            if *a != b {
+                diff.push(format!(
+                    "{}: {} vs {}",
+                    coord::to_algebraic((i % 8, i / 8)),
+                    tile_name(*a),
+                    tile_name(b)
+                ));
+            }
+        }
+
+        let rights = [
+            ("K", Color::White, CastleSide::KingSide),
+            ("Q", Color::White, CastleSide::QueenSide),
+            ("k", Color::Black, CastleSide::KingSide),
+            ("q", Color::Black, CastleSide::QueenSide),
+        ];
+        for (name, color, side) in rights {
+            match (
+                self.has_castling_right(color, side),
+                other.has_castling_right(color, side),
+            ) {
+                (true, false) => diff.push(format!("castling {} lost", name)),
+                (false, true) => diff.push(format!("castling {} gained", name)),
+                _ => (),
+            }
+        }
+
+        if self.en_passant() != other.en_passant() {
+            let ep_name = |ep: Option<(usize, usize)>| match ep {
+                Some(tile) => coord::to_algebraic(tile),
+                None => "-".to_string(),
             };
 
-            let rank = '1' as usize + (7 - ep_y);
-            let file = 'a' as usize + ep_x;
+            diff.push(format!(
+                "en passant: {} vs {}",
+                ep_name(self.en_passant()),
+                ep_name(other.en_passant())
+            ));
+        }
 
-            format!("{}{}", char::from(file as u8), char::from(rank as u8))
-        } else {
-            "-".to_string()
-        };
+        if self.halfmove_clock != other.halfmove_clock {
+            diff.push(format!(
+                "halfmove clock: {} vs {}",
+                self.halfmove_clock, other.halfmove_clock
+            ));
+        }
+        if self.fullmove_number != other.fullmove_number {
+            diff.push(format!(
+                "fullmove number: {} vs {}",
+                self.fullmove_number, other.fullmove_number
+            ));
+        }
 
-        format!("{} {} {} {}", board, turn, castling, en_passant)
+        diff
     }
 }
 
+/// How the castling rights are written in a FEN string
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum CastlingNotation {
+    /// `KQkq`, what normal chess uses
+    #[default]
+    Standard,
+    /// The files of the castling rooks, `HAha`, what Shredder-FEN uses for Chess960
+    Shredder,
+    /// `KQkq`, with the file of the rook only when it isn't the outermost rook on its side
+    ///
+    /// A [`Game`] only castles with the rooks in the corners, which always are the outermost
+    /// rooks, so this is written the same as [`CastlingNotation::Standard`].
+    XFen,
+}
+
+/// Options for how [`Game::fen_with_options`] writes a FEN string
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct FenOptions {
+    /// How the castling rights are written
+    pub castling: CastlingNotation,
+    /// If the half move clock and full move number are written at the end
+    pub move_counters: bool,
+}
+
 fn castling_part(fen_part: &str) -> Result<[bool; 4], FromFenError> {
     if fen_part == "-" {
         return Ok([false; 4]);
     }
 
     let mut castling: [bool; 4] = [false; 4];
-    let chars = fen_part.chars().collect::<Vec<char>>();
 
-    if chars.len() > 4 {
+    if fen_part.chars().count() > 4 {
         return Err(FromFenError::IncorrectLength);
     }
 
-    if chars.len() != chars.iter().collect::<HashSet<&char>>().len() {
-        return Err(FromFenError::RepeatingCharactersInCastlingPart);
-    }
-
-    for c in chars {
-        match c {
-            'K' => castling[0] = true,
-            'Q' => castling[1] = true,
-            'k' => castling[2] = true,
-            'q' => castling[3] = true,
+    // Shredder-FEN and X-FEN write the file of the rook instead, which only can be a corner
+    for c in fen_part.chars() {
+        let right = match c {
+            'K' | 'H' => 0,
+            'Q' | 'A' => 1,
+            'k' | 'h' => 2,
+            'q' | 'a' => 3,
+            'B'..='G' | 'b'..='g' => return Err(FromFenError::UnsupportedCastlingFile),
             _ => return Err(FromFenError::UnknownCharacter),
+        };
+
+        if castling[right] {
+            return Err(FromFenError::RepeatingCharactersInCastlingPart);
         }
+        castling[right] = true;
     }
 
     Ok(castling)
 }
 
+/// Internal helper that returns the pawn that can be captured by moving to an en passant tile
+///
+/// # Returns
+/// * `Option<(usize, usize)>` - The tile of the pawn in front of the en passant tile, or None if
+///   there isn't a pawn of the color that just moved there
+pub(super) fn en_passant_pawn(
+    board: &Board,
+    turn: Color,
+    (ep_x, ep_y): (usize, usize),
+) -> Option<(usize, usize)> {
+    let ep_y = match turn {
+        Color::White => ep_y.checked_sub(1)?,
+        Color::Black => ep_y + 1,
+    };
+
+    board
+        .get_tile(ep_x, ep_y)
+        .filter(|piece| piece.piece_type == PieceType::Pawn && piece.color != turn)
+        .map(|_| (ep_x, ep_y))
+}
+
 fn en_passant(fen_part: &str) -> Result<Option<(usize, usize)>, FromFenError> {
     if fen_part == "-" {
         return Ok(None);
     }
 
-    let chars = fen_part.chars().collect::<Vec<char>>();
-
-    if chars.len() != 2 {
-        return Err(FromFenError::IncorrectAmountOfTiles);
+    if fen_part.chars().count() != 2 {
+        return Err(FromFenError::InvalidEnPassant);
     }
 
-    let file = match chars[0] {
-        'a' => 0,
-        'b' => 1,
-        'c' => 2,
-        'd' => 3,
-        'e' => 4,
-        'f' => 5,
-        'g' => 6,
-        'h' => 7,
-        _ => return Err(FromFenError::UnknownCharacter),
-    };
-
-    // Yes, this is super odd, but i accidentally made the board uppside down, oops...
-    let rank = match chars[1] {
-        '1' => 7,
-        '2' => 6,
-        '3' => 5,
-        '4' => 4,
-        '5' => 3,
-        '6' => 2,
-        '7' => 1,
-        '8' => 0,
-        _ => return Err(FromFenError::UnknownCharacter),
-    };
-
-    Ok(Some((file, rank)))
+    coord::from_algebraic(fen_part)
+        .map(Some)
+        .ok_or(FromFenError::UnknownCharacter)
 }
 
 #[cfg(test)]
@@ -215,6 +474,233 @@ mod tests {
         for fen in fens_to_test {
             let board = Game::from_fen(fen).unwrap();
             assert_eq!(board.fen(), fen);
+
+            let reparsed = Game::from_fen(&board.fen()).unwrap();
+            assert_eq!(board, reparsed, "{:?}", board.fen_diff(&reparsed));
+        }
+    }
+
+    #[test]
+    pub fn fen_with_castling_notations() {
+        // A Chess960 position with the king and rooks on the same tiles as normal chess
+        let fen = "rnqbknbr/pppppppp/8/8/8/8/PPPPPPPP/RNQBKNBR w KQkq - 0 1";
+        let game = Game::from_fen(fen).unwrap();
+
+        let with = |castling| FenOptions {
+            castling,
+            move_counters: true,
+        };
+
+        let shredder = game.fen_with_options(with(CastlingNotation::Shredder));
+        assert_eq!(
+            shredder,
+            "rnqbknbr/pppppppp/8/8/8/8/PPPPPPPP/RNQBKNBR w HAha - 0 1"
+        );
+        assert_eq!(game.fen_with_options(with(CastlingNotation::XFen)), fen);
+        assert_eq!(
+            game.fen_with_options(FenOptions::default()),
+            "rnqbknbr/pppppppp/8/8/8/8/PPPPPPPP/RNQBKNBR w KQkq -"
+        );
+
+        assert_eq!(Game::from_fen(&shredder).unwrap(), game);
+        assert_eq!(Game::from_fen(fen).unwrap(), game);
+
+        let game = Game::from_fen("r3k3/8/8/8/8/8/8/4K2R b Ka - 3 40").unwrap();
+        let shredder = game.fen_with_options(with(CastlingNotation::Shredder));
+        assert_eq!(shredder, "r3k3/8/8/8/8/8/8/4K2R b Ha - 3 40");
+        assert_eq!(Game::from_fen(&shredder).unwrap(), game);
+
+        assert!(matches!(
+            Game::from_fen("rnqbknbr/pppppppp/8/8/8/8/PPPPPPPP/RNQBKNBR w HAhb -"),
+            Err(FromFenError::UnsupportedCastlingFile)
+        ));
+        assert!(matches!(
+            Game::from_fen("rnqbknbr/pppppppp/8/8/8/8/PPPPPPPP/RNQBKNBR w KHkq -"),
+            Err(FromFenError::RepeatingCharactersInCastlingPart)
+        ));
+    }
+
+    #[test]
+    pub fn canonical_fen_drops_uncapturable_en_passant() {
+        // A black pawn next to the pawn can capture it
+        let fen = "4k3/8/8/8/3pP3/8/8/4K3 b - e3";
+        assert_eq!(Game::from_fen(fen).unwrap().canonical_fen(), fen);
+
+        // No black pawn next to the pawn
+        let game = Game::from_fen("4k3/8/8/8/p3P3/8/8/4K3 b - e3").unwrap();
+        assert_eq!(game.canonical_fen(), "4k3/8/8/8/p3P3/8/8/4K3 b - -");
+        assert_eq!(
+            game.canonical_fen(),
+            Game::from_fen("4k3/8/8/8/p3P3/8/8/4K3 b - -")
+                .unwrap()
+                .canonical_fen()
+        );
+
+        // The black pawn is pinned to the king by the bishop, so the capture isn't legal
+        let game = Game::from_fen("7B/8/8/8/3pP3/8/8/k3K3 b - e3").unwrap();
+        assert_eq!(game.fen(), "7B/8/8/8/3pP3/8/8/k3K3 b - e3");
+        assert_eq!(game.canonical_fen(), "7B/8/8/8/3pP3/8/8/k3K3 b - -");
+    }
+
+    #[test]
+    pub fn fen_diff_single_tile() {
+        let a = Game::start_pos();
+        let b = Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPP1PPP/RNBQKBNR w KQkq -").unwrap();
+
+        assert_eq!(a.fen_diff(&b), vec!["e2: white pawn vs empty"]);
+        assert_eq!(b.fen_diff(&a), vec!["e2: empty vs white pawn"]);
+        assert!(a.fen_diff(&a).is_empty());
+
+        let later =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3 12").unwrap();
+        assert_eq!(
+            a.fen_diff(&later),
+            vec!["halfmove clock: 0 vs 3", "fullmove number: 1 vs 12"]
+        );
+    }
+
+    #[test]
+    pub fn turn_is_case_insensitive() {
+        let board = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+
+        for (turn, color) in [
+            ("w", Color::White),
+            ("W", Color::White),
+            ("b", Color::Black),
+            ("B", Color::Black),
+            ("w.", Color::White),
+        ] {
+            let game = Game::from_fen(&format!("{} {} KQkq -", board, turn)).unwrap();
+            assert_eq!(game.turn(), color);
+        }
+
+        for turn in ["x", "white", "-"] {
+            assert!(matches!(
+                Game::from_fen(&format!("{} {} KQkq -", board, turn)),
+                Err(FromFenError::UnknownTurn)
+            ));
+        }
+    }
+
+    #[test]
+    pub fn from_fen_reads_the_move_counters() {
+        let game =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        assert_eq!(game.halfmove_clock(), 0);
+        assert_eq!(game.fullmove_number(), 1);
+        assert_eq!(
+            game,
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3").unwrap()
+        );
+
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 37 82").unwrap();
+        assert_eq!(game.halfmove_clock(), 37);
+        assert_eq!(game.fullmove_number(), 82);
+
+        assert!(matches!(
+            Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - x 82"),
+            Err(FromFenError::InvalidMoveCounter)
+        ));
+        assert!(matches!(
+            Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 37"),
+            Err(FromFenError::IncorrectAmountOfParts)
+        ));
+    }
+
+    #[test]
+    pub fn castling_rights_in_any_order() {
+        let board = "r3k2r/8/8/8/8/8/8/R3K2R w";
+        let expected = Game::from_fen(&format!("{} KQkq -", board)).unwrap();
+
+        for castling in ["qKkQ", "kqKQ", "QKqk", "qkQK"] {
+            let game = Game::from_fen(&format!("{} {} -", board, castling)).unwrap();
+            assert_eq!(game.castling_rights(), expected.castling_rights());
+            assert_eq!(game.fen(), expected.fen());
         }
+
+        let game = Game::from_fen(&format!("{} qK -", board)).unwrap();
+        assert_eq!(game.fen(), "r3k2r/8/8/8/8/8/8/R3K2R w Kq -");
+
+        let game = Game::from_fen(&format!("{} - -", board)).unwrap();
+        assert_eq!(game.castling_rights(), CastlingRights::default());
+        assert_eq!(game.fen(), "r3k2r/8/8/8/8/8/8/R3K2R w - -");
+
+        assert!(matches!(
+            Game::from_fen(&format!("{} qKq -", board)),
+            Err(FromFenError::RepeatingCharactersInCastlingPart)
+        ));
+        assert!(matches!(
+            Game::from_fen(&format!("{} KQkqK -", board)),
+            Err(FromFenError::IncorrectLength)
+        ));
+    }
+
+    #[test]
+    pub fn en_passant_without_a_capturing_pawn() {
+        // No black pawn can capture the pawn on e4
+        let fen = "4k3/8/8/8/4P3/8/8/4K3 b - e3";
+        let game = Game::from_fen(fen).unwrap();
+        assert_eq!(Game::from_fen_lenient_ep(fen).unwrap(), game);
+
+        assert_eq!(game.en_passant(), Some((4, 2)));
+        assert_eq!(game.fen(), fen);
+        assert_eq!(game.canonical_fen(), "4k3/8/8/8/4P3/8/8/4K3 b - -");
+
+        // The pawn that moved two tiles has to be there
+        assert!(matches!(
+            Game::from_fen("4k3/8/8/8/8/8/8/4K3 b - e3"),
+            Err(FromFenError::InvalidEnPassant)
+        ));
+
+        // There is no tile in front of the last rank
+        assert!(matches!(
+            Game::from_fen("4k3/8/8/8/8/8/8/4K3 b - a8"),
+            Err(FromFenError::InvalidEnPassant)
+        ));
+
+        // The en passant part names a single tile
+        assert!(matches!(
+            Game::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3e"),
+            Err(FromFenError::InvalidEnPassant)
+        ));
+    }
+
+    #[test]
+    pub fn from_fen_with_too_few_parts() {
+        for fen in [
+            "",
+            "   ",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0",
+        ] {
+            assert!(
+                matches!(
+                    Game::from_fen(fen),
+                    Err(FromFenError::IncorrectAmountOfParts)
+                ),
+                "{:?}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    pub fn from_fen_with_extra_whitespace() {
+        let expected = Game::start_pos();
+
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -  ",
+            "  rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR  w   KQkq\t-",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0  1\n",
+        ] {
+            assert_eq!(Game::from_fen(fen).unwrap(), expected, "{:?}", fen);
+        }
+
+        assert!(matches!(
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w  -"),
+            Err(FromFenError::IncorrectAmountOfParts)
+        ));
     }
 }
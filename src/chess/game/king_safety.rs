@@ -0,0 +1,97 @@
+use crate::{Color, Game, PieceType};
+
+use super::attack_tables::{self, KING_ATTACKS};
+use super::attacks::piece_attacks;
+
+impl Game {
+    /// Returns how hard the enemy pieces are pressing on the king of a color
+    ///
+    /// The zone around the king is the tile of the king and the tiles next to it. Every enemy
+    /// piece adds its weight for each tile in the zone it attacks, minor pieces weigh 2, rooks 3
+    /// and queens 5, pawns and the king aren't counted. Rooks and queens see through other rooks
+    /// and queens of their color, so pieces stacked on a file all count.
+    ///
+    /// # Arguments
+    /// * `color` - The color of the king under pressure
+    ///
+    /// # Returns
+    /// * `i32` - The pressure, 0 if no enemy piece attacks the zone or there is no king
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Game};
+    ///
+    /// // The rook on e1 attacks e8 and e7
+    /// let game = Game::from_fen("4k3/8/8/8/8/8/8/4R1K1 b - -").unwrap();
+    /// assert_eq!(game.king_attack_zone_pressure(Color::Black), 2 * 3);
+    /// ```
+    pub fn king_attack_zone_pressure(&self, color: Color) -> i32 {
+        let (king_x, king_y) = match self.board.get_king_pos(color) {
+            Some(pos) => pos,
+            None => return 0,
+        };
+
+        let king_tile = king_y * 8 + king_x;
+        let zone = KING_ATTACKS[king_tile] | 1 << king_tile;
+
+        let enemy = color.opposite();
+        let occupied = self.board.occupied();
+        let queens = self.board.pieces(PieceType::Queen, enemy);
+        let straight = self.board.pieces(PieceType::Rook, enemy) | queens;
+        let diagonal = self.board.pieces(PieceType::Bishop, enemy) | queens;
+
+        let mut pressure = 0;
+
+        for (x, y) in attack_tables::tiles(self.board.color_pieces(enemy)) {
+            let piece = self
+                .board
+                .get_tile(x, y)
+                .expect("The tile is set in the bitboard");
+
+            let weight = match piece.piece_type {
+                PieceType::Knight | PieceType::Bishop => 2,
+                PieceType::Rook => 3,
+                PieceType::Queen => 5,
+                PieceType::Pawn | PieceType::King => continue,
+            };
+
+            // Sliders look through the sliders behind or in front of them on the same line
+            let see_through = match piece.piece_type {
+                PieceType::Rook => straight,
+                PieceType::Bishop => diagonal,
+                PieceType::Queen => straight | diagonal,
+                _ => 0,
+            };
+
+            let attacks = piece_attacks(piece, y * 8 + x, occupied & !see_through);
+            pressure += weight * (attacks & zone).count_ones() as i32;
+        }
+
+        pressure
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn stacked_pieces_on_an_open_file_press_the_king() {
+        let quiet = Game::from_fen("6k1/5pp1/8/8/8/8/5PP1/R2QK3 w - -").unwrap();
+        let rooks = Game::from_fen("6k1/5pp1/8/8/8/8/5PPR/4K2R w - -").unwrap();
+        let rooks_and_queen = Game::from_fen("6k1/5pp1/8/8/8/7Q/5PPR/4K2R w - -").unwrap();
+
+        let quiet = quiet.king_attack_zone_pressure(Color::Black);
+        let rooks = rooks.king_attack_zone_pressure(Color::Black);
+        let rooks_and_queen = rooks_and_queen.king_attack_zone_pressure(Color::Black);
+
+        assert_eq!(quiet, 0);
+        // Both rooks attack h7 and h8, the one behind sees through the one in front
+        assert_eq!(rooks, 2 * 2 * 3);
+        assert_eq!(rooks_and_queen, rooks + 2 * 5);
+
+        // The white king isn't under pressure
+        let game = Game::from_fen("6k1/5pp1/8/8/8/7Q/5PPR/4K2R w - -").unwrap();
+        assert_eq!(game.king_attack_zone_pressure(Color::White), 0);
+    }
+}
@@ -0,0 +1,182 @@
+use alloc::vec::Vec;
+
+use crate::{coord, error::MoveError, Game, Move, PieceType};
+
+impl Game {
+    /// Parses a move in Long Algebraic Notation (LAN) for the current turn
+    ///
+    /// Both tiles are written out, so there is never any disambiguation. The piece letter and the
+    /// separator are optional, but if they are given they must match the move. Check and
+    /// annotation suffixes are ignored and castling is written like in SAN.
+    ///
+    /// # Arguments
+    /// * `lan` - The move in LAN, e.g `Ng1-f3`, `e2-e4`, `e5xd6` or `e7-e8=Q`
+    ///
+    /// # Returns
+    /// * `Result<Move, MoveError>` - The legal move the LAN describes or an error if the LAN
+    ///   couldn't be parsed or isn't legal
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, Move};
+    ///
+    /// let game = Game::start_pos();
+    /// assert_eq!(
+    ///     game.parse_lan("Ng1-f3").unwrap(),
+    ///     Move::Quiet { from: (6, 0), to: (5, 2) }
+    /// );
+    /// ```
+    pub fn parse_lan(&self, lan: &str) -> Result<Move, MoveError> {
+        let lan = lan.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        if matches!(lan, "O-O" | "0-0" | "O-O-O" | "0-0-0") {
+            return self.parse_san(lan);
+        }
+
+        let mut chars = lan.chars().collect::<Vec<char>>();
+
+        // Piece letters are always uppercase, a lowercase `b` is the b file
+        let piece_type = match chars.first() {
+            Some(c) if c.is_ascii_uppercase() => {
+                let piece_type = PieceType::from_fen_char(*c)
+                    .ok_or(MoveError::parse_failed("Unknown piece letter"))?
+                    .0;
+                chars.remove(0);
+                Some(piece_type)
+            }
+            _ => None,
+        };
+
+        // Promotions are written as either `e8=Q` or `e8Q`
+        let promotion = match chars.last() {
+            Some(c @ ('N' | 'B' | 'R' | 'Q')) => {
+                let promotion = PieceType::from_fen_char(*c).expect("Matched above").0;
+
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+
+                Some(promotion)
+            }
+            _ => None,
+        };
+
+        let (from, capture, to) = match chars.as_slice() {
+            [from_file, from_rank, separator, to_file, to_rank] => (
+                [*from_file, *from_rank],
+                match separator {
+                    '-' => Some(false),
+                    'x' => Some(true),
+                    _ => return Err(MoveError::parse_failed("The separator must be - or x")),
+                },
+                [*to_file, *to_rank],
+            ),
+            [from_file, from_rank, to_file, to_rank] => {
+                ([*from_file, *from_rank], None, [*to_file, *to_rank])
+            }
+            _ => {
+                return Err(MoveError::parse_failed(
+                    "A LAN move is two squares with an optional separator",
+                ))
+            }
+        };
+
+        let tile = |[file, rank]: [char; 2]| {
+            Some((coord::file_from_char(file)?, coord::rank_from_char(rank)?))
+        };
+        let from = tile(from).ok_or(MoveError::parse_failed("Invalid square"))?;
+        let to = tile(to).ok_or(MoveError::parse_failed("Invalid square"))?;
+
+        // Castling is only written as O-O and O-O-O
+        let mv = self.find_move(from, to, promotion)?;
+        if mv.is_castle() {
+            return Err(MoveError::Illegal);
+        }
+
+        let piece = self
+            .board
+            .get_tile(from.0, from.1)
+            .expect("Legal moves always start on a piece");
+
+        if piece_type.is_some_and(|p| p != piece.piece_type)
+            || capture.is_some_and(|c| c != mv.is_capture())
+        {
+            return Err(MoveError::Illegal);
+        }
+
+        Ok(mv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_lan_moves() {
+        let game = Game::start_pos();
+
+        assert_eq!(
+            game.parse_lan("e2-e4").unwrap(),
+            Move::DoublePawnPush {
+                from: (4, 1),
+                to: (4, 3)
+            }
+        );
+        assert_eq!(game.parse_lan("Ng1-f3").unwrap().to(), (5, 2));
+        assert_eq!(game.parse_lan("b1c3").unwrap().to(), (2, 2));
+
+        // The piece letter and the separator must match the move
+        assert!(matches!(game.parse_lan("Bg1-f3"), Err(MoveError::Illegal)));
+        assert!(matches!(game.parse_lan("e2xe4"), Err(MoveError::Illegal)));
+        assert!(matches!(game.parse_lan("e2-e5"), Err(MoveError::Illegal)));
+        assert!(matches!(
+            game.parse_lan("e2/e4"),
+            Err(MoveError::ParseFailed { .. })
+        ));
+        assert!(matches!(
+            game.parse_lan("e4"),
+            Err(MoveError::ParseFailed { .. })
+        ));
+    }
+
+    #[test]
+    pub fn parse_lan_captures_and_promotions() {
+        // White can take en passant on d6 and promote on e8 or f8
+        let game = Game::from_fen("5n1k/4P3/8/3pP3/8/8/8/4K3 w - d6").unwrap();
+
+        let mv = game.parse_lan("e5xd6").unwrap();
+        assert_eq!(mv.capture(), Some((3, 4)));
+
+        assert_eq!(
+            game.parse_lan("e7-e8=Q").unwrap().promotion(),
+            Some(PieceType::Queen)
+        );
+        assert_eq!(
+            game.parse_lan("e7xf8N+").unwrap().promotion(),
+            Some(PieceType::Knight)
+        );
+        assert!(matches!(
+            game.parse_lan("e7-e8"),
+            Err(MoveError::PromotionRequired)
+        ));
+        assert!(matches!(
+            game.parse_lan("e4-e5"),
+            Err(MoveError::NoPieceOnSource)
+        ));
+        assert!(matches!(
+            game.parse_lan("Nf8-g6"),
+            Err(MoveError::WrongColorPiece)
+        ));
+        assert!(matches!(game.parse_lan("e7-f8=Q"), Err(MoveError::Illegal)));
+    }
+
+    #[test]
+    pub fn parse_lan_castling() {
+        let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq -").unwrap();
+
+        assert!(game.parse_lan("O-O").unwrap().is_king_side_castle());
+        assert!(game.parse_lan("0-0-0+").unwrap().is_queen_side_castle());
+    }
+}
@@ -0,0 +1,172 @@
+use alloc::vec::Vec;
+
+use crate::{error::GameApplyMoveError, Game, GameHistory, Move};
+
+/// A game together with the moves that led to it, so repetitions are detected and moves can be
+/// taken back
+///
+/// The Polyglot key of every earlier position is kept next to it, so checking for a repetition
+/// only compares numbers. Positions from before the last capture or pawn move can never come back,
+/// so only the keys since then are compared.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Game, GameLine};
+///
+/// let mut line = GameLine::new(Game::start_pos());
+///
+/// for san in ["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1", "Ng8"] {
+///     line.apply_move(line.game().parse_san(san).unwrap()).unwrap();
+/// }
+/// assert!(line.is_threefold_repetition());
+///
+/// line.undo_move();
+/// assert!(!line.is_threefold_repetition());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameLine {
+    game: Game,
+    /// The positions before each move, the oldest first
+    previous: Vec<Game>,
+    /// The Polyglot keys of `previous`
+    keys: Vec<u64>,
+    moves: Vec<Move>,
+}
+
+impl GameLine {
+    /// Starts a line from a position, positions before it are never counted as repetitions
+    pub fn new(game: Game) -> GameLine {
+        GameLine {
+            game,
+            ..GameLine::default()
+        }
+    }
+
+    /// Returns the current position
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns the moves that have been applied, the oldest first
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Applies a move to the current position, see [`Game::apply_move`]
+    ///
+    /// # Arguments
+    /// * `mv` - The move to apply
+    ///
+    /// # Returns
+    /// * `Result<(), GameApplyMoveError>` - Nothing or an error if the move couldn't be applied,
+    ///   then the line is left unchanged
+    pub fn apply_move(&mut self, mv: Move) -> Result<(), GameApplyMoveError> {
+        let mut game = self.game;
+        game.apply_move(mv)?;
+
+        self.keys.push(self.game.polyglot_key());
+        self.previous.push(self.game);
+        self.moves.push(mv);
+        self.game = game;
+
+        Ok(())
+    }
+
+    /// Takes back the last move
+    ///
+    /// # Returns
+    /// * `Option<Move>` - The move that was taken back or None if no moves have been applied
+    pub fn undo_move(&mut self) -> Option<Move> {
+        self.game = self.previous.pop()?;
+        self.keys.pop();
+        self.moves.pop()
+    }
+
+    /// Returns how many times the current position occurred earlier in the line
+    pub fn repetitions(&self) -> usize {
+        let key = self.game.polyglot_key();
+
+        self.keys
+            .iter()
+            .rev()
+            .take(self.game.halfmove_clock() as usize)
+            .filter(|k| **k == key)
+            .count()
+    }
+
+    /// Returns if the current position has occurred at least three times
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetitions() + 1 >= 3
+    }
+
+    /// Returns if the game is drawn, the same as [`Game::is_draw`] with the history of the line
+    pub fn is_draw(&self) -> bool {
+        self.is_threefold_repetition() || self.game.is_draw(&GameHistory::new())
+    }
+
+    /// Returns the current position, dropping the earlier ones
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+}
+
+impl From<Game> for GameLine {
+    fn from(game: Game) -> Self {
+        GameLine::new(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn threefold_by_shuffling_knights() {
+        let mut line = GameLine::new(Game::start_pos());
+        let play = |line: &mut GameLine, san: &str| {
+            line.apply_move(line.game().parse_san(san).unwrap())
+                .unwrap();
+        };
+
+        for san in ["Nc3", "Nc6", "Nb1", "Nb8"] {
+            play(&mut line, san);
+        }
+        assert_eq!(line.repetitions(), 1);
+        assert!(!line.is_draw());
+
+        for san in ["Nc3", "Nc6", "Nb1", "Nb8"] {
+            play(&mut line, san);
+        }
+        assert_eq!(line.repetitions(), 2);
+        assert!(line.is_threefold_repetition());
+        assert!(line.is_draw());
+        assert_eq!(line.moves().len(), 8);
+
+        // After a pawn move the earlier positions can never come back
+        play(&mut line, "e4");
+        assert_eq!(line.repetitions(), 0);
+
+        assert_eq!(line.undo_move(), Some(line.game().parse_san("e4").unwrap()));
+        assert!(line.is_threefold_repetition());
+        assert_eq!(line.game().fen(), Game::start_pos().fen());
+
+        // There is no piece on e4
+        let invalid = Move::Quiet {
+            from: (4, 3),
+            to: (4, 4),
+        };
+        assert!(line.apply_move(invalid).is_err());
+        assert_eq!(line.moves().len(), 8);
+    }
+
+    #[test]
+    pub fn undo_move_to_the_start() {
+        let mut line = GameLine::from(Game::start_pos());
+        assert_eq!(line.undo_move(), None);
+
+        line.apply_move(line.game().parse_san("d4").unwrap())
+            .unwrap();
+        assert!(line.undo_move().is_some());
+        assert_eq!(line, GameLine::new(Game::start_pos()));
+    }
+}
@@ -0,0 +1,356 @@
+//! Magic bitboards for the attacks of sliding pieces
+//!
+//! For every tile the tiles a rook or bishop could be blocked on are masked out of the
+//! occupancy, multiplied with a magic number and shifted, which gives a unique index into a
+//! table of precomputed attacks. The magic numbers were found with a random search and are
+//! embedded below, the tables are built the first time they are needed.
+//!
+//! Building the tables once needs `std::sync::OnceLock`, so without the `std` feature the attacks
+//! are found by walking along the rays instead, which is slower but needs no tables.
+
+// The magics are only used to build the tables
+#![cfg_attr(not(feature = "std"), allow(dead_code))]
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+use super::gen_pseudo_legal_moves::{BISHOP_DIRS, ROOK_DIRS};
+
+/// The magic lookup for one tile
+struct Magic {
+    /// The tiles that can block the piece, edges are excluded since a piece there can't block
+    /// anything further away
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    /// Where the attacks of this tile start in the shared table
+    offset: usize,
+}
+
+struct Magics {
+    rook: Vec<Magic>,
+    bishop: Vec<Magic>,
+    attacks: Vec<u64>,
+}
+
+#[cfg(feature = "std")]
+static MAGICS: OnceLock<Magics> = OnceLock::new();
+
+/// The magic numbers for rooks, one for each tile
+const ROOK_MAGICS: [u64; 64] = [
+    0x0880_0080_2010_4000,
+    0x50C0_0040_1000_2008,
+    0x8480_2000_8010_0008,
+    0x0100_0408_1001_0021,
+    0x0600_2008_1004_0201,
+    0x0780_0380_0400_2200,
+    0x0380_0200_0100_0880,
+    0x8100_0028_9200_4100,
+    0x4500_8020_8000_4008,
+    0x0020_4020_1000_4003,
+    0x0000_8080_2000_1000,
+    0x4120_8010_0208_0084,
+    0x0001_0004_1100_0800,
+    0x8043_0002_0400_0900,
+    0x284C_0081_0408_1002,
+    0x3242_0004_0100_4082,
+    0x1040_0080_0020_5082,
+    0x0010_10C0_2000_4004,
+    0x00A0_1900_2000_4100,
+    0x05C7_4200_0820_9200,
+    0x44A0_8080_0400_0800,
+    0x0411_0100_0804_0002,
+    0x0817_0400_0810_2261,
+    0x3000_0600_0058_8124,
+    0x2040_0080_8000_4024,
+    0x0000_8101_0040_0021,
+    0x4048_2082_0040_1200,
+    0x0100_1002_8008_0084,
+    0x0202_0066_0008_2010,
+    0x0802_0012_0048_1084,
+    0x0000_1024_0002_A128,
+    0x0100_0082_0001_0044,
+    0x0180_2040_1080_0080,
+    0x0001_8120_0280_4008,
+    0x0400_4100_1900_2002,
+    0x0420_8008_0080_1000,
+    0x0200_8588_0100_1100,
+    0x0002_0004_0080_0280,
+    0x0000_0E28_0400_5015,
+    0x1124_8000_6080_0100,
+    0x0012_8A22_4000_8000,
+    0x8000_8022_0102_0040,
+    0x1001_0020_0041_0012,
+    0x0001_0090_0109_0022,
+    0x1081_0008_0005_0050,
+    0x0000_0400_0200_8080,
+    0x0000_0201_8804_0050,
+    0x0000_0044_2082_0001,
+    0x8000_4285_0208_2A00,
+    0x0100_8040_0020_0080,
+    0x0180_1000_8220_0480,
+    0x9000_0A20_1042_0200,
+    0x0004_1101_3800_0D00,
+    0x8902_0080_0204_0080,
+    0x041A_2210_1809_4400,
+    0x4620_0F11_8400_4200,
+    0x8005_0480_0610_2041,
+    0x0000_4000_1081_0021,
+    0x0212_0440_1008_8022,
+    0x0401_0020_6910_010D,
+    0x0089_0008_0002_0411,
+    0x0215_0004_0002_0801,
+    0x4810_1008_0912_0884,
+    0x0460_0104_0020_4882,
+];
+
+/// The magic numbers for bishops, one for each tile
+const BISHOP_MAGICS: [u64; 64] = [
+    0x0140_8200_8402_9080,
+    0x0002_5002_1081_0001,
+    0x0208_8618_4202_0400,
+    0x1004_0410_8000_0001,
+    0x0421_1040_0000_0220,
+    0x2000_8844_4001_0900,
+    0x8044_0462_0834_0800,
+    0x1080_A02A_0814_4021,
+    0x4000_2088_8109_0401,
+    0x284C_0809_1464_0040,
+    0x2200_2108_1081_9000,
+    0x0502_0240_8300_0080,
+    0x0000_0202_1100_0000,
+    0x0502_5110_2210_0000,
+    0x0224_0901_0150_4100,
+    0x0600_8C44_1088_0862,
+    0x0820_0C84_0890_0108,
+    0x0420_80A4_1840_8504,
+    0x0802_0004_0802_080A,
+    0x2448_0100_8200_4000,
+    0x4088_804C_04A0_2400,
+    0x0002_0002_2210_1210,
+    0x0224_1011_0108_A200,
+    0x2440_8000_2084_1090,
+    0x0110_0800_4008_6100,
+    0x0004_0440_2001_2440,
+    0x2004_1200_8108_0100,
+    0x1101_0040_0400_4200,
+    0x0110_0400_0080_2100,
+    0x0020_4500_8210_0201,
+    0x00A2_0080_0848_0844,
+    0x0002_2022_4080_8808,
+    0x0048_200B_0010_1200,
+    0x0888_0248_0022_4840,
+    0x0400_4448_0010_02E0,
+    0x0000_4201_8008_0280,
+    0x1084_0404_0000_1010,
+    0xC0B0_1212_0088_2080,
+    0x0010_0503_0400_4C20,
+    0x0001_0061_0002_0100,
+    0x1084_1104_4101_5004,
+    0x0101_0082_6110_1014,
+    0x0122_1400_2400_A800,
+    0x6000_0014_1401_0800,
+    0x0220_1802_0820_0402,
+    0x4402_8428_0220_8200,
+    0x0061_0800_8080_2100,
+    0x1002_0401_0824_0200,
+    0xA008_8848_0804_4260,
+    0x4002_8204_9004_C001,
+    0x0420_1500_80D0_1C00,
+    0x0208_8020_2288_0500,
+    0x0084_8404_1044_0002,
+    0x0088_1012_1083_0388,
+    0x0020_0230_8127_0001,
+    0x0008_3001_0208_2004,
+    0x0401_8140_5804_4001,
+    0xF021_1084_0082_1000,
+    0x2681_0002_0200_9201,
+    0x0000_0204_0842_1A00,
+    0x0000_0029_0405_0402,
+    0x0004_8020_1411_8208,
+    0x4000_2004_5042_0040,
+    0x0020_6810_0080_2040,
+];
+
+/// Returns the tiles a rook on a tile attacks
+///
+/// # Arguments
+/// * `tile` - The tile of the rook, `y * 8 + x`
+/// * `occupied` - The bitboard of all pieces on the board
+pub(super) fn rook_attacks(tile: usize, occupied: u64) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        let magics = magics();
+        lookup(&magics.rook[tile], &magics.attacks, occupied)
+    }
+
+    #[cfg(not(feature = "std"))]
+    ray_attacks(tile, occupied, ROOK_DIRS)
+}
+
+/// Returns the tiles a bishop on a tile attacks
+///
+/// # Arguments
+/// * `tile` - The tile of the bishop, `y * 8 + x`
+/// * `occupied` - The bitboard of all pieces on the board
+pub(super) fn bishop_attacks(tile: usize, occupied: u64) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        let magics = magics();
+        lookup(&magics.bishop[tile], &magics.attacks, occupied)
+    }
+
+    #[cfg(not(feature = "std"))]
+    ray_attacks(tile, occupied, BISHOP_DIRS)
+}
+
+/// Internal helper that finds the attacks in the table
+fn lookup(magic: &Magic, attacks: &[u64], occupied: u64) -> u64 {
+    let index = ((occupied & magic.mask).wrapping_mul(magic.magic) >> magic.shift) as usize;
+    attacks[magic.offset + index]
+}
+
+/// Internal helper that returns the magics, building the tables the first time
+#[cfg(feature = "std")]
+fn magics() -> &'static Magics {
+    MAGICS.get_or_init(|| {
+        let mut attacks = Vec::new();
+
+        let rook = (0..64)
+            .map(|tile| build_magic(tile, ROOK_MAGICS[tile], ROOK_DIRS, &mut attacks))
+            .collect();
+        let bishop = (0..64)
+            .map(|tile| build_magic(tile, BISHOP_MAGICS[tile], BISHOP_DIRS, &mut attacks))
+            .collect();
+
+        Magics {
+            rook,
+            bishop,
+            attacks,
+        }
+    })
+}
+
+/// Internal helper that fills in the attacks of a tile for every occupancy and appends them to
+/// the shared table
+fn build_magic(tile: usize, magic: u64, dirs: &[(i32, i32)], attacks: &mut Vec<u64>) -> Magic {
+    let mask = blocker_mask(tile, dirs);
+    let bits = mask.count_ones();
+    let shift = 64 - bits;
+    let offset = attacks.len();
+
+    attacks.resize(offset + (1 << bits), 0);
+
+    // Walk through every subset of the mask
+    let mut occupied = 0u64;
+    loop {
+        let index = (occupied.wrapping_mul(magic) >> shift) as usize;
+        let attack = ray_attacks(tile, occupied, dirs);
+
+        debug_assert!(
+            attacks[offset + index] == 0 || attacks[offset + index] == attack,
+            "The magic numbers can't have collisions"
+        );
+        attacks[offset + index] = attack;
+
+        occupied = occupied.wrapping_sub(mask) & mask;
+        if occupied == 0 {
+            break;
+        }
+    }
+
+    Magic {
+        mask,
+        magic,
+        shift,
+        offset,
+    }
+}
+
+/// Internal helper that returns the tiles along the rays that could block a sliding piece
+fn blocker_mask(tile: usize, dirs: &[(i32, i32)]) -> u64 {
+    let (x, y) = ((tile % 8) as i32, (tile / 8) as i32);
+    let mut mask = 0;
+
+    for dir in dirs {
+        let mut c_x = x + dir.0;
+        let mut c_y = y + dir.1;
+
+        // Stop one tile before the edge
+        while (0..8).contains(&(c_x + dir.0)) && (0..8).contains(&(c_y + dir.1)) {
+            mask |= 1 << (c_y * 8 + c_x);
+            c_x += dir.0;
+            c_y += dir.1;
+        }
+    }
+
+    mask
+}
+
+/// Internal helper that walks along the rays until a piece is hit, used to build the tables and
+/// without the `std` feature
+fn ray_attacks(tile: usize, occupied: u64, dirs: &[(i32, i32)]) -> u64 {
+    let (x, y) = ((tile % 8) as i32, (tile / 8) as i32);
+    let mut attacks = 0;
+
+    for dir in dirs {
+        let mut c_x = x + dir.0;
+        let mut c_y = y + dir.1;
+
+        while (0..8).contains(&c_x) && (0..8).contains(&c_y) {
+            let bit = 1 << (c_y * 8 + c_x);
+            attacks |= bit;
+
+            if occupied & bit != 0 {
+                break;
+            }
+
+            c_x += dir.0;
+            c_y += dir.1;
+        }
+    }
+
+    attacks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rng, XorShiftRng};
+
+    #[test]
+    pub fn magic_attacks_match_ray_scan() {
+        let mut rng = XorShiftRng::new(99);
+
+        for _ in 0..200 {
+            // Sparse and dense occupancies
+            let occupied = rng.next_u64() & rng.next_u64();
+            let dense = rng.next_u64() | rng.next_u64();
+
+            for tile in 0..64 {
+                for occupied in [occupied, dense] {
+                    assert_eq!(
+                        rook_attacks(tile, occupied),
+                        ray_attacks(tile, occupied, ROOK_DIRS)
+                    );
+                    assert_eq!(
+                        bishop_attacks(tile, occupied),
+                        ray_attacks(tile, occupied, BISHOP_DIRS)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn rook_attacks_stop_at_blockers() {
+        // A rook on a1 with a piece on a4 and on d1
+        let occupied = 1 << (4 * 8) | 1 << (7 * 8 + 3);
+        let attacks = rook_attacks(7 * 8, occupied);
+
+        assert_eq!(attacks.count_ones(), 6);
+        assert_ne!(attacks & 1 << (4 * 8), 0);
+        assert_eq!(attacks & 1 << (3 * 8), 0);
+    }
+}
@@ -0,0 +1,156 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{Game, Move, MoveList};
+
+impl Game {
+    /// Searches for a forced checkmate for the current turn
+    ///
+    /// The shortest mate is found first, so a mate in one is returned even if a mate in two also
+    /// exists. The opponent is assumed to defend as well as possible, at every reply the one that
+    /// delays the mate the longest is picked for the principal variation.
+    ///
+    /// # Arguments
+    /// * `max_moves` - The maximum number of moves by the current turn, a mate in two is two moves
+    ///
+    /// # Returns
+    /// * `Option<Vec<Move>>` - The principal variation ending in checkmate, the moves of both
+    ///   colors in the order they are played, or None if there is no forced mate within
+    ///   `max_moves` moves
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, Move};
+    ///
+    /// // Mate in one with the rook along the back rank
+    /// let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - -").unwrap();
+    /// let pv = game.find_mate(1).unwrap();
+    ///
+    /// assert_eq!(pv, vec![Move::Quiet { from: (0, 0), to: (0, 7) }]);
+    /// ```
+    pub fn find_mate(&self, max_moves: u32) -> Option<Vec<Move>> {
+        let moves = self.shortest_mate(max_moves)?;
+        Some(self.mate_line(moves))
+    }
+
+    /// Internal helper that returns the least amount of moves the current turn needs to force mate
+    fn shortest_mate(&self, max_moves: u32) -> Option<u32> {
+        (1..=max_moves).find(|moves| self.mating_move(*moves).is_some())
+    }
+
+    /// Internal helper that finds a move that forces mate in at most `moves` moves
+    fn mating_move(&self, moves: u32) -> Option<Move> {
+        let mut candidates = MoveList::new();
+        self.gen_all_moves_into(&mut candidates);
+        // Forcing moves first, they are the most likely to mate
+        candidates.sort_by_key(|m| -self.move_order_score(m));
+
+        candidates.into_iter().find(|m| {
+            let mut game = *self;
+            game.apply_move(*m)
+                .expect("gen_all_moves only returns valid moves");
+
+            if moves == 1 {
+                return game.is_checkmate();
+            }
+
+            let mut replies = MoveList::new();
+            game.gen_all_moves_into(&mut replies);
+
+            if replies.is_empty() {
+                return game.is_check();
+            }
+
+            replies.into_iter().all(|reply| {
+                let mut game = game;
+                game.apply_move(reply)
+                    .expect("gen_all_moves only returns valid moves");
+                game.mating_move(moves - 1).is_some()
+            })
+        })
+    }
+
+    /// Internal helper that builds the principal variation of a mate in exactly `moves` moves
+    fn mate_line(&self, moves: u32) -> Vec<Move> {
+        let first = self
+            .mating_move(moves)
+            .expect("The mate was already proven");
+
+        let mut game = *self;
+        game.apply_move(first)
+            .expect("gen_all_moves only returns valid moves");
+
+        let mut line = vec![first];
+
+        // Pick the reply that delays the mate the longest
+        let longest_defence = game.legal_moves_iter().max_by_key(|reply| {
+            let mut game = game;
+            game.apply_move(*reply)
+                .expect("gen_all_moves only returns valid moves");
+            game.shortest_mate(moves - 1)
+        });
+
+        if let Some(reply) = longest_defence {
+            game.apply_move(reply)
+                .expect("gen_all_moves only returns valid moves");
+            let remaining = game
+                .shortest_mate(moves - 1)
+                .expect("The mate was already proven");
+
+            line.push(reply);
+            line.extend(game.mate_line(remaining));
+        }
+
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that a line is legal and ends in checkmate
+    fn assert_mating_line(game: &Game, pv: &[Move]) {
+        let mut game = *game;
+
+        for mv in pv {
+            assert!(game.gen_all_moves().unwrap().contains(mv), "{:?}", mv);
+            game.apply_move(*mv).unwrap();
+        }
+
+        assert!(game.is_checkmate());
+    }
+
+    #[test]
+    pub fn mate_in_two() {
+        // Morphy, 1. Ra6 bxa6 2. b7#
+        let game = Game::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - -").unwrap();
+
+        assert_eq!(game.find_mate(1), None);
+
+        let pv = game.find_mate(2).unwrap();
+        assert_eq!(pv.len(), 3);
+        assert_eq!(pv[0], game.parse_san("Ra6").unwrap());
+        assert_mating_line(&game, &pv);
+    }
+
+    #[test]
+    pub fn mate_in_three() {
+        // 1. Ra6 f6 2. Bxf6+ Rg7 3. Rxa8#
+        let game = Game::from_fen("r5rk/5p1p/5R2/4B3/8/8/7P/7K w - -").unwrap();
+
+        assert_eq!(game.find_mate(2), None);
+
+        let pv = game.find_mate(3).unwrap();
+        assert_eq!(pv.len(), 5);
+        assert_eq!(pv[0], game.parse_san("Ra6").unwrap());
+        assert_mating_line(&game, &pv);
+    }
+
+    #[test]
+    pub fn no_mate_without_material() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K2N w - -").unwrap();
+
+        assert_eq!(game.find_mate(2), None);
+        assert_eq!(Game::start_pos().find_mate(1), None);
+    }
+}
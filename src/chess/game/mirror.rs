@@ -0,0 +1,94 @@
+use crate::Game;
+
+impl Game {
+    /// Returns the game flipped upside down with the colors swapped
+    ///
+    /// The board is mirrored with [`Board::mirror_vertical`](crate::Board::mirror_vertical), the
+    /// turn is flipped and the castling rights and en passant tile are moved to the other side,
+    /// so the mirrored game is the same position for the other color.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::from_fen("4k2r/8/8/8/8/8/4P3/4K3 w k -").unwrap();
+    /// assert_eq!(game.mirror_vertical().fen(), "4k3/4p3/8/8/8/8/8/4K2R b K -");
+    /// ```
+    pub fn mirror_vertical(&self) -> Game {
+        Game {
+            board: self.board.mirror_vertical(),
+            turn: self.turn.opposite(),
+            en_passant: self.en_passant.map(|(x, y)| (x, 7 - y)),
+            castling_rights: self.castling_rights.mirror(),
+            ..*self
+        }
+    }
+
+    /// Returns the game from the perspective of the opponent, the same as
+    /// [`Game::mirror_vertical`]
+    ///
+    /// A symmetric evaluation gives the flipped game the same score, since the score is from the
+    /// side to move.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::from_fen("4k2r/8/8/8/8/8/4P3/4K3 w k -").unwrap();
+    /// assert_eq!(game.flipped(), game.mirror_vertical());
+    /// assert_eq!(game.evaluate(), game.flipped().evaluate());
+    /// ```
+    pub fn flipped(&self) -> Game {
+        self.mirror_vertical()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn mirror_vertical_swaps_castling_rights() {
+        let start = Game::start_pos();
+        let mirrored = start.mirror_vertical();
+        assert_eq!(
+            mirrored.fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq -"
+        );
+        assert_eq!(mirrored.mirror_vertical(), start);
+
+        let game = Game::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w Qk d6").unwrap();
+        let mirrored = game.mirror_vertical();
+        assert_eq!(mirrored.fen(), "r3k2r/8/8/8/3Pp3/8/8/R3K2R b Kq d3");
+        assert_eq!(mirrored.mirror_vertical(), game);
+
+        // The mirrored position has the same moves, mirrored
+        assert_eq!(
+            game.gen_all_moves().unwrap().len(),
+            mirrored.gen_all_moves().unwrap().len()
+        );
+    }
+
+    #[test]
+    pub fn mirroring_twice_gives_the_same_fen() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w Kq f6 0 3",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 12 40",
+            "4k3/8/8/8/3pP3/8/8/4K3 b - e3",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            let mirrored = game.mirror_vertical();
+
+            assert_ne!(mirrored.fen(), game.fen());
+            assert_eq!(mirrored.turn(), game.turn().opposite());
+            assert_eq!(mirrored.halfmove_clock(), game.halfmove_clock());
+            assert_eq!(mirrored.mirror_vertical().fen(), game.fen());
+            assert_eq!(mirrored.mirror_vertical(), game);
+
+            assert_eq!(game.flipped(), mirrored);
+            assert_eq!(game.flipped().flipped().fen(), game.fen());
+        }
+    }
+}
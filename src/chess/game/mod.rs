@@ -1,9 +1,49 @@
-use crate::{Board, Color, PieceType};
+#[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{coord, Board, Color, PieceType};
 
 mod apply_move;
+mod attack_tables;
+mod attacks;
+mod builder;
+pub use builder::GameBuilder;
+mod bytes;
+mod castling;
+pub use castling::{CastleSide, CastlingRights};
+mod describe;
+mod draw;
+pub use draw::{GameHistory, Outcome};
+mod evasions;
 mod fen;
+pub use fen::{CastlingNotation, FenOptions};
 mod gen_pseudo_legal_moves;
-use super::Move;
+mod king_safety;
+mod lan;
+mod line;
+pub use line::GameLine;
+mod magic;
+mod mate;
+mod mirror;
+mod move_cache;
+pub use move_cache::MoveCache;
+mod move_kind;
+pub use move_kind::MoveKind;
+mod move_order;
+mod null_move;
+pub use null_move::NullMove;
+mod perft;
+mod polyglot_key;
+mod polyglot_random;
+mod random_move;
+mod san;
+mod see;
+mod uci;
+mod validate;
+use super::{Move, MoveList};
 
 /// A game of chess
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -12,20 +52,115 @@ pub struct Game {
     turn: Color,
     /// Stores a pawn that can be captured en passant
     en_passant: Option<(usize, usize)>,
+    castling_rights: CastlingRights,
+    /// The amount of half moves since the last capture or pawn move
+    halfmove_clock: u32,
+    /// Starts at 1 and is incremented after every move by black
+    fullmove_number: u32,
+}
 
-    white_kingside_castle: bool,
-    white_queenside_castle: bool,
-    black_kingside_castle: bool,
-    black_queenside_castle: bool,
+impl Default for Game {
+    /// Returns a game with the starting position
+    fn default() -> Self {
+        Game::start_pos()
+    }
 }
 
 impl Game {
+    /// Returns a game with the starting position, the same as [`Game::start_pos`]
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::new();
+    /// assert_eq!(game.fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -");
+    /// ```
+    pub fn new() -> Game {
+        Game::start_pos()
+    }
+
     /// Returns a game with the starting position
     pub fn start_pos() -> Game {
         Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -")
             .expect("This fen string is valid")
     }
 
+    /// Creates a game from a board without the rest of a FEN string
+    ///
+    /// There are no castling rights or en passant, the half move clock is 0 and the full move
+    /// number 1. Like [`Game::set_board`] the position isn't checked, use [`crate::GameBuilder`]
+    /// to make sure the position is valid.
+    ///
+    /// # Arguments
+    /// * `board` - The pieces of the game
+    /// * `turn` - The color to move
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color, Game};
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3").unwrap();
+    /// let game = Game::from_board(board, Color::Black);
+    ///
+    /// assert_eq!(game.fen(), "4k3/8/8/8/8/8/8/R3K3 b - -");
+    /// ```
+    pub fn from_board(board: Board, turn: Color) -> Game {
+        Game {
+            board,
+            turn,
+            en_passant: None,
+            castling_rights: CastlingRights::default(),
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    /// Creates a game from a map of algebraic tiles to pieces, like the JSON of a board editor
+    ///
+    /// The castling rights are guessed with [`CastlingRights::infer`], otherwise the game is the
+    /// same as from [`Game::from_board`] and the position isn't checked.
+    ///
+    /// # Arguments
+    /// * `map` - The pieces, keyed by tiles like `e1`
+    /// * `turn` - The color to move
+    ///
+    /// # Returns
+    /// * `Option<Game>` - The game, or None if a key isn't a tile
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use fritiofr_chess::{Color, Game, PieceType};
+    ///
+    /// let map = HashMap::from([
+    ///     ("e1".to_string(), (PieceType::King, Color::White)),
+    ///     ("h1".to_string(), (PieceType::Rook, Color::White)),
+    ///     ("e8".to_string(), (PieceType::King, Color::Black)),
+    /// ]);
+    /// let game = Game::from_square_map(&map, Color::White).unwrap();
+    ///
+    /// assert_eq!(game.fen(), "4k3/8/8/8/8/8/8/4K2R w K -");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_square_map(map: &HashMap<String, (PieceType, Color)>, turn: Color) -> Option<Game> {
+        let mut tiles = [[None; 8]; 8];
+
+        for (square, (piece_type, color)) in map {
+            let (x, y) = coord::from_algebraic(square)?;
+            tiles[y][x] = Some(crate::Piece {
+                piece_type: *piece_type,
+                color: *color,
+            });
+        }
+
+        let board = Board::from_array(tiles);
+        let mut game = Game::from_board(board, turn);
+        game.castling_rights = CastlingRights::infer(&board);
+
+        Some(game)
+    }
+
     /// Returns the Board that for the game
     ///
     /// # Return
@@ -38,74 +173,170 @@ impl Game {
     ///
     /// **This will reset en passant and castling**
     ///
+    /// The board isn't checked, call [`Game::validate`] afterwards or set up the position with a
+    /// [`GameBuilder`] to make sure it is legal.
+    ///
     /// # Arguments
     /// * `board` - The board to set
     pub fn set_board(&mut self, board: Board) {
-        self.white_kingside_castle = false;
-        self.white_queenside_castle = false;
-        self.black_kingside_castle = false;
-        self.black_queenside_castle = false;
+        self.castling_rights = CastlingRights::default();
 
         self.en_passant = None;
 
         self.board = board;
     }
 
+    /// Returns the Board of the game
+    ///
+    /// # Return
+    /// * `&Board` - The board of the game
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the current turn
+    ///
+    /// # Return
+    /// * `Color` - The current turn
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    /// Returns the tile a pawn can move to when capturing en passant
+    ///
+    /// This is the tile written in a FEN string, the tile behind the pawn that moved two steps.
+    ///
+    /// # Return
+    /// * `Option<(usize, usize)>` - The en passant tile or None if no pawn can be captured en
+    ///   passant
+    pub fn en_passant(&self) -> Option<(usize, usize)> {
+        self.en_passant.map(|(x, y)| match self.turn {
+            Color::White => (x, y + 1),
+            Color::Black => (x, y - 1),
+        })
+    }
+
+    /// Returns the castling rights of the game
+    ///
+    /// The castling rights only tells if the king or rook has moved, see
+    /// [`Game::can_castle_kingside`] for if castling is possible right now.
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+
+    /// Returns the amount of half moves since the last capture or pawn move, used for the fifty
+    /// move rule
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    /// Returns the number of the current full move, starting at 1 and incremented after every
+    /// move by black
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
     /// Returns the current turn
     ///
+    /// Kept for backwards compatibility, use [`Game::turn`] instead.
+    ///
     /// # Return
     /// * `Color` - The current turn
-    pub fn get_turn(&mut self) -> Color {
+    #[deprecated(note = "use `Game::turn` instead")]
+    pub fn get_turn(&self) -> Color {
         self.turn
     }
 
     /// Sets the current turn
     ///
-    /// **This will reset en passant**
+    /// This is an unchecked edit, the position might not be reachable afterwards. Call
+    /// [`Game::validate`] to find a position where the side that just moved is left in check.
+    ///
+    /// **This will reset en passant if the turn changes**, since the pawn that could be captured
+    /// belongs to the new turn
     ///
     /// # Arguments
-    /// * `Color` - The current turn
+    /// * `turn` - The current turn
     pub fn set_turn(&mut self, turn: Color) {
-        self.en_passant = None;
+        if turn != self.turn {
+            self.en_passant = None;
+        }
+
         self.turn = turn;
     }
 
+    /// Returns the game with another turn, see [`Game::set_turn`]
+    ///
+    /// # Arguments
+    /// * `turn` - The new turn
+    pub fn with_turn(mut self, turn: Color) -> Game {
+        self.set_turn(turn);
+        self
+    }
+
+    /// Sets the en passant tile
+    ///
+    /// This is an unchecked edit, nothing makes sure there is a pawn that can be captured. Use
+    /// [`GameBuilder::en_passant`] to get an error instead.
+    ///
+    /// # Arguments
+    /// * `en_passant` - The tile a capturing pawn moves to, the same tile as returned by
+    ///   [`Game::en_passant`], or None to clear it. A tile the current turn can't capture on is
+    ///   ignored and clears en passant
+    pub fn set_en_passant(&mut self, en_passant: Option<(usize, usize)>) {
+        // The en passant tile is stored as the tile of the pawn that can be captured
+        self.en_passant =
+            en_passant
+                .filter(|(x, _)| *x < 8)
+                .and_then(|(x, y)| match (self.turn, y) {
+                    (Color::White, 5) => Some((x, 4)),
+                    (Color::Black, 2) => Some((x, 3)),
+                    _ => None,
+                });
+    }
+
+    /// Returns the game with another en passant tile, see [`Game::set_en_passant`]
+    ///
+    /// # Arguments
+    /// * `en_passant` - The new en passant tile
+    pub fn with_en_passant(mut self, en_passant: Option<(usize, usize)>) -> Game {
+        self.set_en_passant(en_passant);
+        self
+    }
+
+    /// Sets the castling rights
+    ///
+    /// This is an unchecked edit, the rights are set even if the king or rook isn't on its
+    /// starting tile. [`Game::validate`] returns an error for such rights.
+    ///
+    /// # Arguments
+    /// * `castling_rights` - The new castling rights
+    pub fn set_castling_rights(&mut self, castling_rights: CastlingRights) {
+        self.castling_rights = castling_rights;
+    }
+
+    /// Returns the game with other castling rights, see [`Game::set_castling_rights`]
+    ///
+    /// # Arguments
+    /// * `castling_rights` - The new castling rights
+    pub fn with_castling_rights(mut self, castling_rights: CastlingRights) -> Game {
+        self.set_castling_rights(castling_rights);
+        self
+    }
+
     /// Returns if a certain color can capture the other color's king
     fn can_capture_king(&self, color: Color) -> bool {
-        self.board
-            .tiles
-            .iter()
-            .enumerate()
-            .filter(|(_, p)| p.is_some())
-            .map(|(i, p)| {
-                let x = i % 8;
-                let y = i / 8;
-
-                ((x, y), p.unwrap())
-            })
-            .filter(|(_, p)| p.color == color)
-            .map(|((x, y), _)| {
-                self.gen_pseudo_legal_moves(x, y, false)
-                    .unwrap_or(Vec::new())
-            })
-            .flatten()
-            .any(|m| match m {
-                Move::Capture { capture, .. } | Move::CapturePromotion { capture, .. } => {
-                    self.board
-                        .get_tile(capture.0, capture.1)
-                        .unwrap()
-                        .piece_type
-                        == PieceType::King
-                }
-                _ => false,
-            })
+        match self.board.get_king_pos(color.opposite()) {
+            Some((x, y)) => self.is_tile_attacked(x, y, color),
+            None => false,
+        }
     }
 
     /// Returns if the current turn is in check
     ///
     /// # Returns
     /// * `bool` - If the current turn is in check, if it's black to move and black is in check,
-    /// this will return true
+    ///   this will return true
     pub fn is_check(&self) -> bool {
         self.can_capture_king(self.turn.opposite())
     }
@@ -114,42 +345,160 @@ impl Game {
     ///
     /// # Returns
     /// * `bool` - If the current turn is in checkmate, if it's black to move and black is in
-    /// checkmate, this will return true
+    ///   checkmate, this will return true
     pub fn is_checkmate(&self) -> bool {
-        self.is_check() && self.gen_all_moves().is_none()
+        self.is_check() && !self.has_legal_move()
     }
 
     /// Returns if the current turn is in stalemate
     ///
     /// # Returns
     /// * `bool` - If the current turn is in stalemate, if it's black to move and black is in
-    /// stalemate, this will return true
+    ///   stalemate, this will return true
     pub fn is_stalemate(&self) -> bool {
-        !self.is_check() && self.gen_all_moves().is_none()
+        !self.is_check() && !self.has_legal_move()
+    }
+
+    /// Returns the color that won the game by checkmate
+    ///
+    /// # Returns
+    /// * `Option<Color>` - The color that delivered checkmate, or None if the game is still going
+    ///   or drawn
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Game};
+    ///
+    /// let game = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - -").unwrap();
+    /// assert_eq!(game.winner(), Some(Color::White));
+    /// ```
+    pub fn winner(&self) -> Option<Color> {
+        if self.is_checkmate() {
+            Some(self.turn.opposite())
+        } else {
+            None
+        }
     }
 
     /// Returns all moves for the current turn
     ///
     /// # Returns
     /// * `Option<Vec<Move>>` - A vector of all the moves for the current turn, if there are no
-    /// moves, this will return None
+    ///   moves, this will return None
     pub fn gen_all_moves(&self) -> Option<Vec<Move>> {
-        let moves = (0..64)
-            .map(|i| {
-                let x = i % 8;
-                let y = i / 8;
+        let mut moves = MoveList::new();
+        self.gen_all_moves_into(&mut moves);
 
-                (x, y)
-            })
-            .map(|(x, y)| self.gen_moves(x, y).unwrap_or(Vec::new()))
-            .flatten()
-            .collect::<Vec<Move>>();
-
-        if moves.len() == 0 {
+        if moves.is_empty() {
             return None;
         }
 
-        Some(moves)
+        Some(moves.to_vec())
+    }
+
+    /// Generates all moves for the current turn into a move list
+    ///
+    /// This is the same as [`Game::gen_all_moves`] but doesn't allocate, the list is cleared
+    /// before the moves are added.
+    ///
+    /// # Arguments
+    /// * `moves` - The list to fill with the moves
+    pub fn gen_all_moves_into(&self, moves: &mut MoveList) {
+        moves.clear();
+
+        let evasions = self.evasion_mask();
+
+        // In check, only the moves that can get out of check are generated
+        if evasions != !0 {
+            self.gen_evasions_into(evasions, moves);
+            return;
+        }
+
+        for (x, y) in attack_tables::tiles(self.board.color_pieces(self.turn)) {
+            self.gen_legal_moves_into(x, y, evasions, moves);
+        }
+    }
+
+    /// Returns an iterator over all moves for the current turn
+    ///
+    /// The moves are generated into a [`MoveList`] up front, so no heap allocation is made.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::start_pos();
+    /// assert_eq!(game.legal_moves_iter().filter(|m| m.is_double_pawn_push()).count(), 8);
+    /// ```
+    pub fn legal_moves_iter(&self) -> impl Iterator<Item = Move> {
+        let mut moves = MoveList::new();
+        self.gen_all_moves_into(&mut moves);
+
+        moves.into_iter()
+    }
+
+    /// Returns the amount of moves for the current turn
+    ///
+    /// The moves are generated into a [`MoveList`], so no heap allocation is made. This is the
+    /// same as `perft(1)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// assert_eq!(Game::start_pos().count_legal_moves(), 20);
+    /// ```
+    pub fn count_legal_moves(&self) -> usize {
+        let mut moves = MoveList::new();
+        self.gen_all_moves_into(&mut moves);
+
+        moves.len()
+    }
+
+    /// Returns if the current turn has any move
+    ///
+    /// This stops at the first piece that can move, which is much faster than generating every
+    /// move when only checkmate or stalemate matters.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// assert!(Game::start_pos().has_legal_move());
+    ///
+    /// // Stalemate
+    /// let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - -").unwrap();
+    /// assert!(!game.has_legal_move());
+    /// ```
+    pub fn has_legal_move(&self) -> bool {
+        let evasions = self.evasion_mask();
+        let mut moves = MoveList::new();
+
+        if evasions != !0 {
+            self.gen_evasions_into(evasions, &mut moves);
+            return !moves.is_empty();
+        }
+
+        attack_tables::tiles(self.board.color_pieces(self.turn)).any(|(x, y)| {
+            self.gen_legal_moves_into(x, y, evasions, &mut moves);
+            !moves.is_empty()
+        })
+    }
+
+    /// Returns all moves for the current turn grouped by the tile they move from
+    ///
+    /// # Returns
+    /// * `HashMap<(usize, usize), Vec<Move>>` - The moves for every tile that has at least one
+    ///   move, the same moves as [`Game::gen_moves`] returns for that tile
+    #[cfg(feature = "std")]
+    pub fn legal_moves_map(&self) -> HashMap<(usize, usize), Vec<Move>> {
+        let mut map: HashMap<(usize, usize), Vec<Move>> = HashMap::new();
+
+        for m in self.legal_moves_iter() {
+            map.entry(m.from()).or_default().push(m);
+        }
+
+        map
     }
 
     /// Returns all moves for a certain tile and the current turn
@@ -160,40 +509,335 @@ impl Game {
     ///
     /// # Returns
     /// * `Option<Vec<Move>>` - A vector of all the moves for the tile, if there are no moves, this
-    /// will return None. If the piece of x and y is the opposite color of the current turn, this
-    /// will return None
+    ///   will return None. If the piece of x and y is the opposite color of the current turn, this
+    ///   will return None
     pub fn gen_moves(&self, x: usize, y: usize) -> Option<Vec<Move>> {
-        if let Some(piece) = self.board.get_tile(x, y) {
-            if piece.color != self.turn {
-                return None;
-            }
-        } else {
+        let mut moves = MoveList::new();
+        self.gen_legal_moves_into(x, y, self.evasion_mask(), &mut moves);
+
+        if moves.is_empty() {
             return None;
         }
 
-        let moves = self.gen_pseudo_legal_moves(x, y, false);
+        Some(moves.to_vec())
+    }
 
-        if moves.is_none() {
-            return None;
+    /// Internal helper that returns the tiles a piece other than the king has to move to or
+    /// capture on to get out of check
+    ///
+    /// When not in check every tile is allowed, in a single check the checker and the tiles
+    /// between it and the king and in a double check no tile, since only the king can move.
+    fn evasion_mask(&self) -> u64 {
+        let king_pos = match self.board.get_king_pos(self.turn) {
+            Some(king_pos) => king_pos,
+            None => return !0,
+        };
+
+        let checkers = self.attackers_mask(king_pos.0, king_pos.1, self.turn.opposite());
+
+        match checkers.count_ones() {
+            0 => !0,
+            1 => {
+                let i = checkers.trailing_zeros() as usize;
+
+                coord::between((i % 8, i / 8), king_pos)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .fold(checkers, |mask, (x, y)| mask | 1 << (y * 8 + x))
+            }
+            _ => 0,
         }
+    }
 
-        let moves = moves
-            .unwrap()
-            .into_iter()
-            .filter(|m| {
-                let mut game = self.clone();
-                game.apply_move(*m)
-                    .expect("gen_pseudo_legal_moves only returns valid moves");
+    /// Internal helper that adds the legal moves of a tile to a move list
+    ///
+    /// # Arguments
+    /// * `evasions` - The mask from [`Game::evasion_mask`], moves of pieces other than the king
+    ///   that doesn't end up in it are skipped before the expensive legality check
+    fn gen_legal_moves_into(&self, x: usize, y: usize, evasions: u64, moves: &mut MoveList) {
+        let piece = match self.board.get_tile(x, y) {
+            Some(piece) if piece.color == self.turn => piece,
+            _ => return,
+        };
+
+        // The pseudo legal moves are added to the end of the list and then the legal ones are
+        // moved down over the illegal ones
+        let start = moves.len();
+        self.gen_pseudo_legal_moves(x, y, false, moves);
+
+        let mut legal = start;
+        for i in start..moves.len() {
+            let m = moves[i];
+
+            if piece.piece_type != PieceType::King {
+                let (to_x, to_y) = m.to();
+                let mut tiles = 1 << (to_y * 8 + to_x);
+                if let Some((c_x, c_y)) = m.capture() {
+                    tiles |= 1 << (c_y * 8 + c_x);
+                }
+
+                if tiles & evasions == 0 {
+                    continue;
+                }
+            }
+
+            let mut game = *self;
+            game.apply_move(m)
+                .expect("gen_pseudo_legal_moves only returns valid moves");
+
+            if !game.can_capture_king(game.turn) {
+                moves[legal] = m;
+                legal += 1;
+            }
+        }
+
+        moves.truncate(legal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Piece;
+    use std::collections::HashSet;
+
+    #[test]
+    pub fn new_and_default_are_the_starting_position() {
+        let game = Game::new();
+
+        assert_eq!(
+            game.fen_with_options(FenOptions {
+                move_counters: true,
+                ..FenOptions::default()
+            }),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(game, Game::default());
+        assert_eq!(game, Game::start_pos());
+    }
+
+    #[test]
+    pub fn count_legal_moves_of_the_perft_positions() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - -",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq -",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ -",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - -",
+            "R5k1/5ppp/8/8/8/8/8/6K1 b - -",
+            "7k/5Q2/6K1/8/8/8/8/8 b - -",
+            // The pinned bishop has no moves, only the king can move
+            "4k3/4b3/8/8/8/8/8/K3R3 b - -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            let count = game.count_legal_moves();
+
+            assert_eq!(game.has_legal_move(), count > 0, "{}", fen);
+
+            assert_eq!(
+                count,
+                game.gen_all_moves().unwrap_or_default().len(),
+                "{}",
+                fen
+            );
+            assert_eq!(count as u64, game.perft(1), "{}", fen);
+        }
+    }
+
+    #[test]
+    pub fn positions_with_more_than_max_moves() {
+        let game = Game::from_fen("k1BQQQQQ/2Q4Q/BQ5Q/Q6Q/Q6Q/Q1Q4Q/Q6Q/1QQQQQQK w - -").unwrap();
+        let moves = game.gen_all_moves().unwrap();
+
+        assert!(moves.len() > crate::MAX_MOVES);
+        assert_eq!(moves.len(), game.count_legal_moves());
+        assert!(game.mobility(Color::White) as usize >= moves.len());
+    }
+
+    #[test]
+    pub fn winner_is_the_mating_color() {
+        // Fool's mate
+        let game =
+            Game::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq -").unwrap();
+        assert_eq!(game.winner(), Some(Color::Black));
+
+        let game = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - -").unwrap();
+        assert_eq!(game.winner(), Some(Color::White));
+
+        // Stalemate
+        let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - -").unwrap();
+        assert!(game.is_stalemate());
+        assert_eq!(game.winner(), None);
+
+        assert_eq!(Game::start_pos().winner(), None);
+    }
+
+    #[test]
+    pub fn start_position_from_a_square_map() {
+        let mut map = HashMap::new();
+
+        for (x, y) in (0..8).flat_map(|x| [(x, 0), (x, 1), (x, 6), (x, 7)]) {
+            let piece = Game::start_pos().board().get_tile(x, y).unwrap();
+            map.insert(coord::to_algebraic((x, y)), (piece.piece_type, piece.color));
+        }
+
+        assert_eq!(
+            Game::from_square_map(&map, Color::White).unwrap(),
+            Game::start_pos()
+        );
+
+        map.insert("e9".to_string(), (PieceType::Pawn, Color::White));
+        assert_eq!(Game::from_square_map(&map, Color::White), None);
+    }
+
+    #[test]
+    pub fn from_board_has_no_castling_or_en_passant() {
+        // The kings and rooks stand on their starting tiles, but there are still no rights
+        let board = Board::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R").unwrap();
+        let game = Game::from_board(board, Color::White);
 
-                let can_cap = !game.can_capture_king(game.turn);
-                can_cap
+        assert_eq!(game.fen(), "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w - -");
+        assert_eq!(game.castling_rights(), CastlingRights::default());
+        assert_eq!(game.en_passant(), None);
+        assert_eq!(game.halfmove_clock(), 0);
+        assert_eq!(game.fullmove_number(), 1);
+        // Castling would add two more moves
+        assert_eq!(game.gen_all_moves().unwrap().len(), 23);
+    }
+
+    #[test]
+    pub fn accessors_read_the_fen() {
+        let game =
+            Game::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w Kq f6 0 4").unwrap();
+
+        assert_eq!(game.turn(), Color::White);
+        assert_eq!(game.en_passant(), Some((5, 5)));
+        assert_eq!(game.halfmove_clock(), 0);
+        assert_eq!(game.fullmove_number(), 4);
+        assert_eq!(
+            game.castling_rights(),
+            CastlingRights {
+                white_kingside: true,
+                white_queenside: false,
+                black_kingside: false,
+                black_queenside: true,
+            }
+        );
+        assert_eq!(
+            game.board().get_tile(4, 4),
+            Some(Piece {
+                piece_type: PieceType::Pawn,
+                color: Color::White
             })
+        );
+
+        let game = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3").unwrap();
+        assert_eq!(game.turn(), Color::Black);
+        assert_eq!(game.en_passant(), Some((4, 2)));
+        assert_eq!(game.castling_rights(), CastlingRights::default());
+
+        let game =
+            Game::from_fen("r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 1 5")
+                .unwrap();
+        assert_eq!(game.turn(), Color::White);
+        assert_eq!(game.en_passant(), None);
+        assert_eq!(game.halfmove_clock(), 1);
+        assert_eq!(game.fullmove_number(), 5);
+    }
+
+    #[test]
+    pub fn promotions_generate_every_piece() {
+        // The pawn on b7 can push to b8 or capture on a8
+        let game = Game::from_fen("n3k3/1P6/8/8/8/8/8/4K3 w - -").unwrap();
+
+        let promotions = game
+            .gen_moves(1, 6)
+            .unwrap()
+            .into_iter()
+            .filter(|m| m.is_promotion())
             .collect::<Vec<Move>>();
+        assert_eq!(promotions.len(), 8);
 
-        if moves.len() == 0 {
-            return None;
+        for piece_type in [
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+        ] {
+            assert!(promotions.contains(&Move::QuietPromotion {
+                from: (1, 6),
+                to: (1, 7),
+                promotion: piece_type,
+            }));
+            assert!(promotions.contains(&Move::CapturePromotion {
+                from: (1, 6),
+                to: (0, 7),
+                capture: (0, 7),
+                promotion: piece_type,
+            }));
         }
+    }
+
+    #[test]
+    pub fn editing_the_position_updates_the_fen() {
+        let game = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3").unwrap();
+
+        // Same turn keeps en passant, a new turn clears it
+        assert_eq!(game.with_turn(Color::Black).fen(), game.fen());
+        let mut toggled = game.with_turn(Color::White);
+        assert_eq!(toggled.fen(), "4k3/8/8/8/3pP3/8/8/4K3 w - -");
+
+        toggled.set_turn(Color::Black);
+        assert_eq!(toggled.fen(), "4k3/8/8/8/3pP3/8/8/4K3 b - -");
+        assert_eq!(toggled.with_en_passant(Some((4, 2))), game);
+        // Black captures en passant on the sixth rank, not the third
+        assert_eq!(toggled.with_en_passant(Some((4, 5))).en_passant(), None);
+
+        let game = game.with_castling_rights(CastlingRights {
+            white_kingside: true,
+            black_queenside: true,
+            ..CastlingRights::default()
+        });
+        assert_eq!(game.fen(), "4k3/8/8/8/3pP3/8/8/4K3 b Kq e3");
+    }
+
+    #[test]
+    pub fn legal_moves_map_groups_by_tile() {
+        let game =
+            Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -")
+                .unwrap();
+        let map = game.legal_moves_map();
+
+        for (from, moves) in &map {
+            assert_eq!(Some(moves.clone()), game.gen_moves(from.0, from.1));
+        }
+
+        let flattened = map.into_values().flatten().collect::<Vec<Move>>();
+        let moves = game.gen_all_moves().unwrap();
+        assert_eq!(flattened.len(), moves.len());
+        assert_eq!(
+            flattened.into_iter().collect::<HashSet<Move>>(),
+            moves.into_iter().collect::<HashSet<Move>>()
+        );
+    }
+
+    #[test]
+    pub fn en_passant_cant_expose_the_king() {
+        // Capturing en passant removes both pawns from the fifth rank, which leaves the king on
+        // a5 in check from the rook on h5
+        let game = Game::from_fen("8/8/8/KPp4r/8/8/8/7k w - c6").unwrap();
+
+        assert_eq!(game.en_passant(), Some((2, 5)));
+        assert!(game
+            .gen_all_moves()
+            .unwrap()
+            .iter()
+            .all(|m| m.capture().is_none()));
+        assert!(game.parse_san("bxc6").is_err());
 
-        Some(moves)
+        // Without the rook the capture is legal
+        let game = Game::from_fen("8/8/8/KPp5/8/8/8/7k w - c6").unwrap();
+        assert!(game.parse_san("bxc6").is_ok());
     }
 }
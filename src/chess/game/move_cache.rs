@@ -0,0 +1,171 @@
+use alloc::vec::Vec;
+
+use crate::{Game, Move, MoveList};
+
+impl Game {
+    /// Returns all moves for the current turn
+    ///
+    /// The same moves as [`Game::gen_all_moves`], but empty instead of None when there are no
+    /// moves. The moves are generated on every call, use a [`MoveCache`] to ask about the same
+    /// position many times.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let mut game = Game::start_pos();
+    /// assert_eq!(game.legal_moves().len(), 20);
+    ///
+    /// game.apply_move(game.parse_san("e4").unwrap()).unwrap();
+    /// assert_eq!(game.legal_moves(), game.gen_all_moves().unwrap());
+    /// ```
+    pub fn legal_moves(&self) -> Vec<Move> {
+        self.legal_moves_iter().collect()
+    }
+
+    /// Returns the moves of the piece on a tile
+    ///
+    /// The same moves as [`Game::gen_moves`], but empty instead of None when there are no moves.
+    /// See [`MoveCache::legal_moves_from`] for a UI that asks about every tile of a position.
+    ///
+    /// # Arguments
+    /// * `x` - The x coordinate of the tile
+    /// * `y` - The y coordinate of the tile
+    ///
+    /// # Returns
+    /// * `Vec<Move>` - The moves of the piece on the tile, empty if it has no moves or belongs to
+    ///   the other color
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// // The knight on g1
+    /// assert_eq!(Game::start_pos().legal_moves_from(6, 0).len(), 2);
+    /// ```
+    pub fn legal_moves_from(&self, x: usize, y: usize) -> Vec<Move> {
+        self.gen_moves(x, y).unwrap_or_default()
+    }
+}
+
+/// A cache of the legal moves of the last game asked about
+///
+/// The moves are generated once and then reused for as long as the cache is asked about the
+/// same game. The cache compares the whole game, so any change like [`Game::apply_move`] or
+/// [`Game::set_turn`] makes it generate the moves again and it never returns stale moves. Keep
+/// one cache for each game that is asked about, a cache shared by two games generates the moves
+/// every time they alternate.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Game, MoveCache};
+///
+/// let mut game = Game::start_pos();
+/// let mut cache = MoveCache::new();
+///
+/// // The moves are generated once for all the tiles
+/// assert_eq!(cache.legal_moves_from(&game, 6, 0).len(), 2);
+/// assert_eq!(cache.legal_moves_from(&game, 4, 1).len(), 2);
+///
+/// game.apply_move(game.parse_san("e4").unwrap()).unwrap();
+/// assert_eq!(cache.legal_moves(&game).len(), 20);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MoveCache {
+    /// The game the moves belong to, None until the cache is first used
+    game: Option<Game>,
+    moves: MoveList,
+}
+
+impl MoveCache {
+    /// Creates an empty cache
+    pub fn new() -> MoveCache {
+        MoveCache::default()
+    }
+
+    /// Returns all moves for the current turn of a game
+    ///
+    /// The same moves as [`Game::legal_moves`], only generated if the cache holds another game.
+    ///
+    /// # Arguments
+    /// * `game` - The game to get the moves of
+    pub fn legal_moves(&mut self, game: &Game) -> &[Move] {
+        if self.game.as_ref() != Some(game) {
+            self.moves.clear();
+            game.gen_all_moves_into(&mut self.moves);
+            self.game = Some(*game);
+        }
+
+        &self.moves
+    }
+
+    /// Returns the moves of the piece on a tile of a game
+    ///
+    /// The same moves as [`Game::legal_moves_from`], taken from [`MoveCache::legal_moves`].
+    ///
+    /// # Arguments
+    /// * `game` - The game to get the moves of
+    /// * `x` - The x coordinate of the tile
+    /// * `y` - The y coordinate of the tile
+    ///
+    /// # Returns
+    /// * `Vec<Move>` - The moves of the piece on the tile, empty if it has no moves or belongs to
+    ///   the other color
+    pub fn legal_moves_from(&mut self, game: &Game, x: usize, y: usize) -> Vec<Move> {
+        self.legal_moves(game)
+            .iter()
+            .filter(|m| m.from() == (x, y))
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn assert_same_moves(cache: &mut MoveCache, game: &Game) {
+        assert_eq!(game.legal_moves(), game.gen_all_moves().unwrap_or_default());
+        assert_eq!(cache.legal_moves(game), game.legal_moves());
+
+        for y in 0..8 {
+            for x in 0..8 {
+                assert_eq!(
+                    cache.legal_moves_from(game, x, y),
+                    game.gen_moves(x, y).unwrap_or_default(),
+                    "{} {}",
+                    x,
+                    y
+                );
+                assert_eq!(
+                    game.legal_moves_from(x, y),
+                    cache.legal_moves_from(game, x, y)
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn cache_is_never_stale() {
+        let mut cache = MoveCache::new();
+        let mut game =
+            Game::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq -").unwrap();
+        assert_same_moves(&mut cache, &game);
+
+        for san in ["d4", "O-O-O", "Kh1"] {
+            game.apply_move(game.parse_san(san).unwrap()).unwrap();
+            assert_same_moves(&mut cache, &game);
+        }
+
+        game.set_turn(Color::Black);
+        assert_same_moves(&mut cache, &game);
+
+        // Switching between two games refills the cache every time
+        let mate = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - -").unwrap();
+        for _ in 0..2 {
+            assert!(cache.legal_moves(&mate).is_empty());
+            assert_same_moves(&mut cache, &game);
+        }
+    }
+}
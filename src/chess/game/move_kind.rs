@@ -0,0 +1,81 @@
+use crate::{Game, Move};
+
+/// What kind of move a move is, see [`Game::move_kind`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MoveKind {
+    Quiet,
+    /// A pawn moving two tiles from its starting rank
+    DoublePush,
+    Capture,
+    /// A pawn capturing a pawn that just moved two tiles, by moving diagonally to an empty tile
+    EnPassant,
+    CastleKingside,
+    CastleQueenside,
+    Promotion,
+    PromotionCapture,
+}
+
+impl Game {
+    /// Returns what kind of move a move is in this game
+    ///
+    /// The move has to be made from this position, a capture is only told apart from en passant
+    /// by the tile the piece moves to being empty. The move isn't checked to be legal.
+    ///
+    /// # Arguments
+    /// * `mv` - The move to classify
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, MoveKind};
+    ///
+    /// let game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6").unwrap();
+    ///
+    /// assert_eq!(game.move_kind(game.parse_san("exd6").unwrap()), MoveKind::EnPassant);
+    /// assert_eq!(game.move_kind(game.parse_san("e6").unwrap()), MoveKind::Quiet);
+    /// ```
+    pub fn move_kind(&self, mv: Move) -> MoveKind {
+        match mv {
+            Move::Quiet { .. } => MoveKind::Quiet,
+            Move::DoublePawnPush { .. } => MoveKind::DoublePush,
+            Move::Capture { to, .. } if self.board.is_empty(to.0, to.1) => MoveKind::EnPassant,
+            Move::Capture { .. } => MoveKind::Capture,
+            Move::Castle { .. } if mv.is_king_side_castle() => MoveKind::CastleKingside,
+            Move::Castle { .. } => MoveKind::CastleQueenside,
+            Move::QuietPromotion { .. } => MoveKind::Promotion,
+            Move::CapturePromotion { .. } => MoveKind::PromotionCapture,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn move_kinds() {
+        let game = Game::from_fen("r3k2r/1P6/8/8/8/8/4P3/R3K2R w KQkq -").unwrap();
+        let kind = |san| game.move_kind(game.parse_san(san).unwrap());
+
+        assert_eq!(kind("e3"), MoveKind::Quiet);
+        assert_eq!(kind("Kf1"), MoveKind::Quiet);
+        assert_eq!(kind("e4"), MoveKind::DoublePush);
+        assert_eq!(kind("Rxa8+"), MoveKind::Capture);
+        assert_eq!(kind("O-O"), MoveKind::CastleKingside);
+        assert_eq!(kind("O-O-O"), MoveKind::CastleQueenside);
+        assert_eq!(kind("b8=Q"), MoveKind::Promotion);
+        assert_eq!(kind("bxa8=N"), MoveKind::PromotionCapture);
+
+        // A pawn capturing normally and en passant
+        let game = Game::from_fen("4k3/8/8/8/3pP3/5N2/8/4K3 b - e3").unwrap();
+        let kind = |san| game.move_kind(game.parse_san(san).unwrap());
+
+        assert_eq!(kind("dxe3"), MoveKind::EnPassant);
+        assert_eq!(kind("d3"), MoveKind::Quiet);
+
+        let game = Game::from_fen("4k3/8/8/8/3p4/4P3/8/4K3 b - -").unwrap();
+        assert_eq!(
+            game.move_kind(game.parse_san("dxe3").unwrap()),
+            MoveKind::Capture
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use alloc::vec::Vec;
+
+use crate::{Game, Move, PieceType};
+
+impl Game {
+    /// Returns all moves for the current turn ordered so the most promising come first
+    ///
+    /// Captures come first, ordered by most valuable victim and then least valuable attacker
+    /// (MVV-LVA), so capturing a queen with a pawn comes before capturing a pawn with a queen.
+    /// Promotions come with the captures, ordered by the value of the new piece. The rest of the
+    /// moves keep the order they were generated in.
+    ///
+    /// # Returns
+    /// * `Vec<Move>` - The moves, empty if there are no moves
+    pub fn ordered_moves(&self) -> Vec<Move> {
+        let mut moves = self.gen_all_moves().unwrap_or_default();
+        moves.sort_by_key(|m| -self.move_order_score(m));
+        moves
+    }
+
+    /// Internal helper that scores a move for move ordering, higher is searched first
+    pub(crate) fn move_order_score(&self, mv: &Move) -> i32 {
+        // Any capture or promotion comes before any quiet move
+        let mut score = if mv.is_capture() || mv.is_promotion() {
+            10_000
+        } else {
+            0
+        };
+
+        if let Some((c_x, c_y)) = mv.capture() {
+            let (from_x, from_y) = mv.from();
+
+            let victim = self
+                .board
+                .get_tile(c_x, c_y)
+                .expect("Captures always capture a piece");
+            let attacker = self
+                .board
+                .get_tile(from_x, from_y)
+                .expect("Moves always start on a piece");
+
+            score += victim.piece_type.value() * 10 - attacker_value(attacker.piece_type) / 10;
+        }
+
+        if let Some(promotion) = mv.promotion() {
+            score += promotion.value();
+        }
+
+        score
+    }
+}
+
+/// Internal helper that returns how costly a piece is as an attacker in MVV-LVA
+///
+/// The same as [`PieceType::value`], except that the king is the most costly attacker, so king
+/// captures are ordered after captures of the same piece with any other piece.
+fn attacker_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::King => 20_000,
+        _ => piece_type.value(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn captures_ordered_by_mvv_lva() {
+        // The pawn on e4 and the queen on h5 can both take the queen on d5, the queen can also
+        // take the pawn on h7
+        let game = Game::from_fen("2k5/7p/8/3q3Q/4P3/8/8/1N2K3 w - -").unwrap();
+        let moves = game.ordered_moves();
+
+        assert_eq!(moves[0], game.parse_san("exd5").unwrap());
+        assert_eq!(moves[1], game.parse_san("Qxd5").unwrap());
+        assert_eq!(moves[2], game.parse_san("Qxh7").unwrap());
+
+        let knight_move = game.parse_san("Nc3").unwrap();
+        let position = |mv: Move| moves.iter().position(|m| *m == mv).unwrap();
+        assert!(position(game.parse_san("exd5").unwrap()) < position(knight_move));
+        assert!(!moves[3].is_capture());
+        assert_eq!(moves.len(), game.gen_all_moves().unwrap().len());
+    }
+
+    #[test]
+    pub fn king_is_the_most_valuable_attacker() {
+        // The king and the pawn on e4 can both take the pawn on d5
+        let game = Game::from_fen("4k3/8/8/3p4/3KP3/8/8/8 w - -").unwrap();
+        let moves = game.ordered_moves();
+
+        assert_eq!(moves[0], game.parse_san("exd5").unwrap());
+        assert_eq!(moves[1], game.parse_san("Kxd5").unwrap());
+        assert!(!moves[2].is_capture());
+    }
+
+    #[test]
+    pub fn promotions_come_before_quiet_moves() {
+        let game = Game::from_fen("4k3/1P6/8/8/8/8/8/RN2K3 w - -").unwrap();
+        let moves = game.ordered_moves();
+
+        assert_eq!(moves[0], game.parse_san("b8=Q").unwrap());
+        assert!(moves[..4].iter().all(|m| m.is_promotion()));
+    }
+}
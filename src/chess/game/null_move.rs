@@ -0,0 +1,70 @@
+use crate::Game;
+
+/// What is needed to take back a null move, returned by [`Game::make_null_move`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use = "The null move can't be taken back without it"]
+pub struct NullMove {
+    en_passant: Option<(usize, usize)>,
+}
+
+impl Game {
+    /// Passes the turn to the other color without moving a piece
+    ///
+    /// A null move isn't legal in chess, it's used by searches to see how good a position is if
+    /// the opponent gets to move twice. Only the turn is flipped and en passant cleared, the move
+    /// counters are left as they are.
+    ///
+    /// # Returns
+    /// * `NullMove` - What is needed to take the null move back with [`Game::unmake_null_move`]
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Game};
+    ///
+    /// let mut game = Game::start_pos();
+    /// let null_move = game.make_null_move();
+    /// assert_eq!(game.turn(), Color::Black);
+    ///
+    /// game.unmake_null_move(null_move);
+    /// assert_eq!(game, Game::start_pos());
+    /// ```
+    pub fn make_null_move(&mut self) -> NullMove {
+        let null_move = NullMove {
+            en_passant: self.en_passant,
+        };
+
+        self.turn = self.turn.opposite();
+        self.en_passant = None;
+
+        null_move
+    }
+
+    /// Takes back a null move made with [`Game::make_null_move`]
+    ///
+    /// # Arguments
+    /// * `null_move` - The value returned when the null move was made
+    pub fn unmake_null_move(&mut self, null_move: NullMove) {
+        self.turn = self.turn.opposite();
+        self.en_passant = null_move.en_passant;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn unmake_null_move_restores_en_passant() {
+        let game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 3 40").unwrap();
+
+        let mut nulled = game;
+        let null_move = nulled.make_null_move();
+        assert_eq!(nulled.fen(), "4k3/8/8/3pP3/8/8/8/4K3 b - -");
+        assert_eq!(nulled.halfmove_clock(), 3);
+        assert_eq!(nulled.fullmove_number(), 40);
+
+        nulled.unmake_null_move(null_move);
+        assert_eq!(nulled, game);
+        assert_eq!(nulled.en_passant(), Some((3, 5)));
+    }
+}
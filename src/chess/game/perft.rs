@@ -0,0 +1,104 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{Game, MoveList};
+
+impl Game {
+    /// Counts all leaf nodes of the move tree to a certain depth
+    ///
+    /// This is mostly useful for testing the move generator, the counts can be compared to known
+    /// values for a position.
+    ///
+    /// # Arguments
+    /// * `depth` - How many half moves to search
+    ///
+    /// # Returns
+    /// * `u64` - The amount of positions at the given depth
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// assert_eq!(Game::start_pos().perft(2), 400);
+    /// ```
+    pub fn perft(&self, depth: u8) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let mut moves = MoveList::new();
+        self.gen_all_moves_into(&mut moves);
+
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        moves
+            .into_iter()
+            .map(|m| {
+                let mut game = *self;
+                game.apply_move(m)
+                    .expect("gen_all_moves only returns valid moves");
+                game.perft(depth - 1)
+            })
+            .sum()
+    }
+
+    /// Counts all leaf nodes of the move tree to a certain depth, using every core
+    ///
+    /// The root moves are split between the threads of the rayon pool, each of them running
+    /// [`Game::perft`] on its own copy of the game. The result is always the same as [`Game::perft`]. Only available with the
+    /// `parallel` feature.
+    ///
+    /// # Arguments
+    /// * `depth` - How many half moves to search
+    ///
+    /// # Returns
+    /// * `u64` - The amount of positions at the given depth
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// assert_eq!(Game::start_pos().perft_parallel(3), 8902);
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn perft_parallel(&self, depth: u32) -> u64 {
+        // A perft deeper than 255 half moves would never finish anyway
+        let depth = u8::try_from(depth).unwrap_or(u8::MAX);
+
+        if depth <= 1 {
+            return self.perft(depth);
+        }
+
+        let mut moves = MoveList::new();
+        self.gen_all_moves_into(&mut moves);
+
+        moves
+            .par_iter()
+            .map(|m| {
+                let mut game = *self;
+                game.apply_move(*m)
+                    .expect("gen_all_moves only returns valid moves");
+                game.perft(depth - 1)
+            })
+            .sum()
+    }
+}
+
+#[cfg(all(test, feature = "parallel"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn perft_parallel_matches_perft() {
+        let game = Game::start_pos();
+        assert_eq!(game.perft_parallel(5), game.perft(5));
+
+        let game =
+            Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -")
+                .unwrap();
+        assert_eq!(game.perft_parallel(3), game.perft(3));
+        assert_eq!(game.perft_parallel(1), game.perft(1));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{Color, Game, PieceType};
+
+use super::polyglot_random::POLYGLOT_RANDOM;
+
+const CASTLE_OFFSET: usize = 768;
+const EN_PASSANT_OFFSET: usize = 772;
+const TURN_OFFSET: usize = 780;
+
+impl Game {
+    /// Returns the Polyglot key for the position
+    ///
+    /// This is the hash used by Polyglot opening books (`.bin` files), it's computed from the
+    /// pieces, castling rights, en passant and the current turn exactly as the Polyglot book
+    /// format specifies.
+    ///
+    /// # Returns
+    /// * `u64` - The Polyglot key of the position
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::start_pos();
+    /// assert_eq!(game.polyglot_key(), 0x463b96181691fc9c);
+    /// ```
+    pub fn polyglot_key(&self) -> u64 {
+        let mut key = 0;
+
+        for (i, tile) in self.board.tiles.iter().enumerate() {
+            if let Some(piece) = tile {
+                let x = i % 8;
+                let y = i / 8;
+
+                let kind = match piece.piece_type {
+                    PieceType::Pawn => 0,
+                    PieceType::Knight => 1,
+                    PieceType::Bishop => 2,
+                    PieceType::Rook => 3,
+                    PieceType::Queen => 4,
+                    PieceType::King => 5,
+                } * 2
+                    + if piece.color == Color::White { 1 } else { 0 };
+
+                key ^= POLYGLOT_RANDOM[64 * kind + 8 * y + x];
+            }
+        }
+
+        let castling = [
+            self.castling_rights.white_kingside,
+            self.castling_rights.white_queenside,
+            self.castling_rights.black_kingside,
+            self.castling_rights.black_queenside,
+        ];
+
+        for (i, _) in castling.iter().enumerate().filter(|(_, c)| **c) {
+            key ^= POLYGLOT_RANDOM[CASTLE_OFFSET + i];
+        }
+
+        // Polyglot only hashes the en passant file if a pawn of the current turn stands next to
+        // the pawn that can be captured
+        if let Some((ep_x, ep_y)) = self.en_passant {
+            let can_capture = [ep_x.checked_sub(1), Some(ep_x + 1)]
+                .into_iter()
+                .flatten()
+                .filter(|x| *x <= 7)
+                .any(|x| {
+                    self.board
+                        .get_tile(x, ep_y)
+                        .is_some_and(|p| p.piece_type == PieceType::Pawn && p.color == self.turn)
+                });
+
+            if can_capture {
+                key ^= POLYGLOT_RANDOM[EN_PASSANT_OFFSET + ep_x];
+            }
+        }
+
+        if self.turn == Color::White {
+            key ^= POLYGLOT_RANDOM[TURN_OFFSET];
+        }
+
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn polyglot_key_matches_reference_keys() {
+        // Reference keys from the Polyglot book format specification
+        let keys_to_test = vec![
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+                0x463b96181691fc9c,
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3",
+                0x823c9b50fd114196,
+            ),
+            (
+                "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6",
+                0x0756b94461c50fb0,
+            ),
+            (
+                "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq -",
+                0x662fafb965db29d4,
+            ),
+            (
+                "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6",
+                0x22a48b5a8e47ff78,
+            ),
+            (
+                "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq -",
+                0x652a607ca3f242c1,
+            ),
+            (
+                "rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - -",
+                0x00fdd303c946bdd9,
+            ),
+            (
+                "rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3",
+                0x3c8123ea7b067637,
+            ),
+            (
+                "rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq -",
+                0x5c3f9b829b279560,
+            ),
+        ];
+
+        for (fen, key) in keys_to_test {
+            let game = Game::from_fen(fen).unwrap();
+            assert_eq!(game.polyglot_key(), key, "{}", fen);
+        }
+    }
+}
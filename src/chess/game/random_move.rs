@@ -0,0 +1,64 @@
+use crate::{Game, Move, Rng};
+
+impl Game {
+    /// Returns a uniformly random legal move for the current turn
+    ///
+    /// The random number generator is passed in, so the same seed always picks the same move. It
+    /// is the crate's own [`Rng`] trait and not `rand::Rng`, since the crate doesn't depend on
+    /// `rand`. A `rand` generator can still be used by wrapping it in a local type that
+    /// implements [`Rng`], see the example of the trait.
+    ///
+    /// # Arguments
+    /// * `rng` - The random number generator to use
+    ///
+    /// # Returns
+    /// * `Option<Move>` - A random legal move or None if there are no legal moves
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, XorShiftRng};
+    ///
+    /// let game = Game::start_pos();
+    /// let mv = game.random_move(&mut XorShiftRng::new(42)).unwrap();
+    ///
+    /// assert!(game.gen_all_moves().unwrap().contains(&mv));
+    /// ```
+    pub fn random_move<R: Rng>(&self, rng: &mut R) -> Option<Move> {
+        let moves = self.gen_all_moves()?;
+        let i = rng.gen_below(moves.len() as u64) as usize;
+
+        Some(moves[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XorShiftRng;
+
+    #[test]
+    pub fn random_move_is_legal() {
+        let mut rng = XorShiftRng::new(1234);
+        let mut game = Game::start_pos();
+
+        for _ in 0..40 {
+            let mv = match game.random_move(&mut rng) {
+                Some(mv) => mv,
+                None => break,
+            };
+
+            assert!(game.gen_all_moves().unwrap().contains(&mv));
+            game.apply_move(mv).unwrap();
+        }
+
+        // Same seed, same move
+        let game = Game::start_pos();
+        assert_eq!(
+            game.random_move(&mut XorShiftRng::new(5)),
+            game.random_move(&mut XorShiftRng::new(5))
+        );
+
+        let checkmate = Game::from_fen("k7/1Q6/1K6/8/8/8/8/8 b - -").unwrap();
+        assert_eq!(checkmate.random_move(&mut rng), None);
+    }
+}
@@ -0,0 +1,521 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{coord, error::MoveError, Color, Game, Move, PieceType};
+
+impl Game {
+    /// Parses a move in Standard Algebraic Notation (SAN) for the current turn
+    ///
+    /// Check, mate and annotation suffixes like `+`, `#`, `!` and `?` are ignored, castling can be
+    /// written with both `O` and `0`.
+    ///
+    /// # Arguments
+    /// * `san` - The move in SAN, e.g `Nf3`, `exd5` or `O-O`
+    ///
+    /// # Returns
+    /// * `Result<Move, MoveError>` - The legal move the SAN describes or an error if the SAN
+    ///   couldn't be parsed, isn't legal or matches more than one legal move
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, Move};
+    ///
+    /// let game = Game::start_pos();
+    /// let mv = game.parse_san("Nf3").unwrap();
+    ///
+    /// assert_eq!(mv, Move::Quiet { from: (6, 0), to: (5, 2) });
+    /// ```
+    pub fn parse_san(&self, san: &str) -> Result<Move, MoveError> {
+        let san = san.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        let moves = self.gen_all_moves().unwrap_or_default();
+
+        let castle = match san {
+            "O-O" | "0-0" => Some(false),
+            "O-O-O" | "0-0-0" => Some(true),
+            _ => None,
+        };
+
+        if let Some(queen_side) = castle {
+            return moves
+                .into_iter()
+                .find(|m| {
+                    if queen_side {
+                        m.is_queen_side_castle()
+                    } else {
+                        m.is_king_side_castle()
+                    }
+                })
+                .ok_or(MoveError::Illegal);
+        }
+
+        let mut chars = san.chars().collect::<Vec<char>>();
+
+        // Piece letters are always uppercase, a lowercase `b` is the b file
+        let piece_type = match chars.first() {
+            Some(c) if *c != 'P' && c.is_ascii_uppercase() => {
+                PieceType::from_fen_char(*c)
+                    .ok_or(MoveError::parse_failed("Unknown piece letter"))?
+                    .0
+            }
+            Some(_) => PieceType::Pawn,
+            None => return Err(MoveError::parse_failed("The move is empty")),
+        };
+
+        if piece_type != PieceType::Pawn {
+            chars.remove(0);
+        }
+
+        // Promotions are written as either `e8=Q` or `e8Q`
+        let promotion = match chars.last() {
+            Some(c @ ('N' | 'B' | 'R' | 'Q')) if piece_type == PieceType::Pawn => {
+                let promotion = PieceType::from_fen_char(*c).expect("Matched above").0;
+
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+
+                Some(promotion)
+            }
+            _ => None,
+        };
+
+        if chars.len() < 2 {
+            return Err(MoveError::parse_failed(
+                "The move has no destination square",
+            ));
+        }
+
+        let to_rank = chars.pop().expect("Length is checked above");
+        let to_file = chars.pop().expect("Length is checked above");
+        let to = coord::file_from_char(to_file)
+            .zip(coord::rank_from_char(to_rank))
+            .ok_or(MoveError::parse_failed("Invalid destination square"))?;
+
+        let is_capture = chars.last() == Some(&'x');
+        if is_capture {
+            chars.pop();
+        }
+
+        // Whatever is left is disambiguation, either a file, a rank or both
+        let mut from_file = None;
+        let mut from_rank = None;
+        for c in chars {
+            if let Some(file) = coord::file_from_char(c) {
+                from_file = Some(file);
+            } else if let Some(rank) = coord::rank_from_char(c) {
+                from_rank = Some(rank);
+            } else {
+                return Err(MoveError::parse_failed("Unexpected character"));
+            }
+        }
+
+        // A pawn can only move to a different file when capturing, and then the file is given
+        if piece_type == PieceType::Pawn && !is_capture {
+            from_file = Some(to.0);
+        }
+
+        let candidates = moves
+            .into_iter()
+            .filter(|m| {
+                let (from_x, from_y) = m.from();
+                let piece = self
+                    .board
+                    .get_tile(from_x, from_y)
+                    .expect("Generated moves always start on a piece");
+
+                !m.is_castle()
+                    && piece.piece_type == piece_type
+                    && m.to() == to
+                    && from_file.is_none_or(|f| f == from_x)
+                    && from_rank.is_none_or(|r| r == from_y)
+            })
+            .collect::<Vec<Move>>();
+
+        let mut matching = candidates.iter().filter(|m| m.promotion() == promotion);
+
+        let mv = match matching.next() {
+            Some(mv) => *mv,
+            // The pawn can move there, but only by promoting
+            None if candidates.iter().any(|m| m.is_promotion()) => {
+                return Err(MoveError::PromotionRequired)
+            }
+            None => return Err(MoveError::Illegal),
+        };
+
+        if matching.next().is_some() {
+            return Err(MoveError::Ambiguous);
+        }
+
+        Ok(mv)
+    }
+
+    /// Parses a move in Standard Algebraic Notation (SAN) and checks its check suffix
+    ///
+    /// Like [`Game::parse_san`], but a move that gives check has to end with `+`, a move that
+    /// checkmates with `#` and any other move with neither. Annotations like `!` and `?` are
+    /// still ignored.
+    ///
+    /// # Arguments
+    /// * `san` - The move in SAN, e.g `Nf3`, `Bb5+` or `Qxf7#`
+    ///
+    /// # Returns
+    /// * `Result<Move, MoveError>` - The legal move the SAN describes or an error if
+    ///   [`Game::parse_san`] fails or the suffix is wrong
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{error::MoveError, Game};
+    ///
+    /// let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - -").unwrap();
+    ///
+    /// assert!(game.parse_san_strict("Ra8#").is_ok());
+    /// assert!(matches!(game.parse_san_strict("Ra8+"), Err(MoveError::WrongCheckSuffix)));
+    /// ```
+    pub fn parse_san_strict(&self, san: &str) -> Result<Move, MoveError> {
+        let annotated = san.trim().trim_end_matches(['!', '?']);
+        let without_suffix = annotated.trim_end_matches(['+', '#']);
+        let suffix = &annotated[without_suffix.len()..];
+
+        let mv = self.parse_san(without_suffix)?;
+
+        let mut after = *self;
+        after
+            .apply_move(mv)
+            .expect("parse_san only returns legal moves");
+
+        let expected = if after.is_checkmate() {
+            "#"
+        } else if after.is_check() {
+            "+"
+        } else {
+            ""
+        };
+
+        if suffix != expected {
+            return Err(MoveError::WrongCheckSuffix);
+        }
+
+        Ok(mv)
+    }
+
+    /// Writes a move in Standard Algebraic Notation (SAN) for the current turn
+    ///
+    /// The move is disambiguated by file, rank or both if other pieces of the same type can move
+    /// to the same tile, and ends with `+` for check and `#` for checkmate.
+    ///
+    /// # Arguments
+    /// * `mv` - The move to write
+    ///
+    /// # Returns
+    /// * `Result<String, MoveError>` - The move in SAN or an error if the move isn't legal
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, Move};
+    ///
+    /// let game = Game::start_pos();
+    /// let mv = Move::Quiet { from: (6, 0), to: (5, 2) };
+    ///
+    /// assert_eq!(game.to_san(mv).unwrap(), "Nf3");
+    /// ```
+    pub fn to_san(&self, mv: Move) -> Result<String, MoveError> {
+        let moves = self.gen_all_moves().unwrap_or_default();
+        if !moves.contains(&mv) {
+            return Err(MoveError::Illegal);
+        }
+
+        let mut san = if mv.is_king_side_castle() {
+            String::from("O-O")
+        } else if mv.is_queen_side_castle() {
+            String::from("O-O-O")
+        } else {
+            self.san_without_suffix(mv, &moves)
+        };
+
+        let mut after = *self;
+        after
+            .apply_move(mv)
+            .expect("The move was checked to be legal");
+
+        if after.is_checkmate() {
+            san.push('#');
+        } else if after.is_check() {
+            san.push('+');
+        }
+
+        Ok(san)
+    }
+
+    /// Writes a list of moves in Standard Algebraic Notation (SAN), each one from the position
+    /// after the moves before it
+    ///
+    /// # Arguments
+    /// * `moves` - The moves, the first one for the current turn
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, MoveError>` - The moves in SAN or the error of the first move that
+    ///   isn't legal
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, Move};
+    ///
+    /// let game = Game::start_pos();
+    /// let moves = [
+    ///     Move::DoublePawnPush { from: (4, 1), to: (4, 3) },
+    ///     Move::DoublePawnPush { from: (4, 6), to: (4, 4) },
+    /// ];
+    ///
+    /// assert_eq!(game.san_sequence(&moves).unwrap(), vec!["e4", "e5"]);
+    /// ```
+    pub fn san_sequence(&self, moves: &[Move]) -> Result<Vec<String>, MoveError> {
+        let mut game = *self;
+
+        moves
+            .iter()
+            .map(|mv| {
+                let san = game.to_san(*mv)?;
+                game.apply_move(*mv)
+                    .expect("to_san only succeeds for legal moves");
+
+                Ok(san)
+            })
+            .collect()
+    }
+
+    /// Internal helper that writes a move that isn't a castle in SAN without the check suffix
+    fn san_without_suffix(&self, mv: Move, moves: &[Move]) -> String {
+        let (from_x, from_y) = mv.from();
+        let piece_type = self
+            .board
+            .get_tile(from_x, from_y)
+            .expect("Legal moves always start on a piece")
+            .piece_type;
+
+        let mut san = String::new();
+
+        if piece_type == PieceType::Pawn {
+            // Pawn captures start with the file and are never ambiguous otherwise
+            if mv.is_capture() {
+                san.push(coord::file_to_char(from_x));
+            }
+        } else {
+            san.push(piece_type.fen_char(Color::White));
+
+            let others = moves
+                .iter()
+                .filter(|m| {
+                    m.to() == mv.to()
+                        && m.from() != mv.from()
+                        && self
+                            .board
+                            .get_tile(m.from().0, m.from().1)
+                            .is_some_and(|p| p.piece_type == piece_type)
+                })
+                .collect::<Vec<_>>();
+
+            if !others.is_empty() {
+                if others.iter().all(|m| m.from().0 != from_x) {
+                    san.push(coord::file_to_char(from_x));
+                } else if others.iter().all(|m| m.from().1 != from_y) {
+                    san.push(coord::rank_to_char(from_y));
+                } else {
+                    san.push_str(&coord::to_algebraic(mv.from()));
+                }
+            }
+        }
+
+        if mv.is_capture() {
+            san.push('x');
+        }
+
+        san.push_str(&coord::to_algebraic(mv.to()));
+
+        if let Some(promotion) = mv.promotion() {
+            san.push('=');
+            san.push(promotion.fen_char(Color::White));
+        }
+
+        san
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_san_moves() {
+        let game = Game::start_pos();
+
+        assert_eq!(
+            game.parse_san("e4").unwrap(),
+            Move::DoublePawnPush {
+                from: (4, 1),
+                to: (4, 3)
+            }
+        );
+        assert_eq!(
+            game.parse_san("Nc3").unwrap(),
+            Move::Quiet {
+                from: (1, 0),
+                to: (2, 2)
+            }
+        );
+        assert!(matches!(game.parse_san("e5"), Err(MoveError::Illegal)));
+        assert!(matches!(
+            game.parse_san("Zz9"),
+            Err(MoveError::ParseFailed { .. })
+        ));
+    }
+
+    #[test]
+    pub fn parse_san_captures_and_castling() {
+        let game =
+            Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -")
+                .unwrap();
+
+        assert!(game.parse_san("O-O").unwrap().is_king_side_castle());
+        assert!(game.parse_san("O-O-O").unwrap().is_queen_side_castle());
+        assert_eq!(game.parse_san("dxe6").unwrap().capture(), Some((4, 5)));
+        assert_eq!(game.parse_san("Qxf6+").unwrap().to(), (5, 5));
+    }
+
+    #[test]
+    pub fn parse_san_disambiguation_and_promotion() {
+        let game = Game::from_fen("k7/4P3/8/8/8/8/8/KN3N2 w - -").unwrap();
+
+        assert!(matches!(game.parse_san("Nd2"), Err(MoveError::Ambiguous)));
+        assert_eq!(game.parse_san("Nbd2").unwrap().from(), (1, 0));
+        assert_eq!(game.parse_san("Nfd2").unwrap().from(), (5, 0));
+        assert_eq!(
+            game.parse_san("e8=N").unwrap().promotion(),
+            Some(PieceType::Knight)
+        );
+        assert_eq!(
+            game.parse_san("e8Q").unwrap().promotion(),
+            Some(PieceType::Queen)
+        );
+        assert!(matches!(
+            game.parse_san("e8"),
+            Err(MoveError::PromotionRequired)
+        ));
+        assert!(matches!(game.parse_san("e7"), Err(MoveError::Illegal)));
+        assert!(matches!(
+            game.parse_san("Ne"),
+            Err(MoveError::ParseFailed { .. })
+        ));
+    }
+
+    #[test]
+    pub fn san_sequence_disambiguates_at_every_position() {
+        // The knight on b1 goes to d2 alone, later the knights on d2 and g1 can both reach f3 and
+        // the knights on b1 and f3 can both reach d2
+        let start = Game::from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - -").unwrap();
+        let sans = ["Nd2", "Kd8", "Ngf3", "Ke8", "Nb1", "Kd8", "Nfd2"];
+
+        let mut game = start;
+        let mut moves = vec![];
+        for san in sans {
+            let mv = game.parse_san(san).unwrap();
+            game.apply_move(mv).unwrap();
+            moves.push(mv);
+        }
+
+        assert_eq!(start.san_sequence(&moves).unwrap(), sans);
+        assert!(start.san_sequence(&[]).unwrap().is_empty());
+
+        // The second move is for white again, so it isn't legal
+        moves.swap(1, 2);
+        assert!(matches!(
+            start.san_sequence(&moves),
+            Err(MoveError::Illegal)
+        ));
+    }
+
+    #[test]
+    pub fn parse_san_with_suffixes() {
+        let game = Game::start_pos();
+        let nf3 = game.parse_san("Nf3").unwrap();
+        for san in ["Nf3!", "Nf3?", "Nf3!!", "Nf3!?", " Nf3?? "] {
+            assert_eq!(game.parse_san(san).unwrap(), nf3, "{}", san);
+            assert_eq!(game.parse_san_strict(san).unwrap(), nf3, "{}", san);
+        }
+        assert!(matches!(
+            game.parse_san_strict("Nf3+"),
+            Err(MoveError::WrongCheckSuffix)
+        ));
+
+        let game =
+            Game::from_fen("rnbqkbnr/ppppp1pp/5p2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq -").unwrap();
+        let qh5 = game.parse_san("Qh5").unwrap();
+        assert_eq!(game.parse_san("Qh5+").unwrap(), qh5);
+        assert_eq!(game.parse_san_strict("Qh5+").unwrap(), qh5);
+        assert_eq!(game.parse_san_strict("Qh5+!").unwrap(), qh5);
+        for san in ["Qh5", "Qh5#", "Qh5++"] {
+            assert!(
+                matches!(game.parse_san_strict(san), Err(MoveError::WrongCheckSuffix)),
+                "{}",
+                san
+            );
+        }
+
+        // Scholar's mate
+        let game =
+            Game::from_fen("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq -")
+                .unwrap();
+        let qxf7 = game.parse_san("Qxf7").unwrap();
+        assert_eq!(game.parse_san("Qxf7#").unwrap(), qxf7);
+        assert_eq!(game.parse_san_strict("Qxf7#").unwrap(), qxf7);
+        assert!(matches!(
+            game.parse_san_strict("Qxf7+"),
+            Err(MoveError::WrongCheckSuffix)
+        ));
+
+        // The move itself is checked before the suffix
+        assert!(matches!(
+            game.parse_san_strict("Qxf8#"),
+            Err(MoveError::Illegal)
+        ));
+    }
+
+    #[test]
+    pub fn san_round_trip() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq -",
+            "k7/4P3/8/8/8/8/8/KN3N2 w - -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+
+            for mv in game.legal_moves_iter() {
+                let san = game.to_san(mv).unwrap();
+                assert_eq!(game.parse_san(&san).unwrap(), mv, "{} {}", fen, san);
+            }
+        }
+    }
+
+    #[test]
+    pub fn to_san_disambiguation_and_suffixes() {
+        // Knights on b1 and f1 can both go to d2, the rooks on a1 and a3 to a2
+        let game = Game::from_fen("7k/4P3/8/8/8/R7/8/RN3NK1 w - -").unwrap();
+        let san = |s| game.to_san(game.parse_san(s).unwrap()).unwrap();
+
+        assert_eq!(san("Nbd2"), "Nbd2");
+        assert_eq!(san("R1a2"), "R1a2");
+        assert_eq!(san("Nh2"), "Nh2");
+        assert_eq!(san("e8=Q"), "e8=Q+");
+        assert_eq!(san("Rb3"), "Rb3");
+
+        let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - -").unwrap();
+        assert_eq!(game.to_san(game.parse_san("Ra8").unwrap()).unwrap(), "Ra8#");
+
+        let illegal = Move::Quiet {
+            from: (0, 0),
+            to: (1, 1),
+        };
+        assert!(matches!(game.to_san(illegal), Err(MoveError::Illegal)));
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::vec::Vec;
+
+use crate::{Game, Move, PieceType};
+
+impl Game {
+    /// Returns the material won or lost by a move when both sides keep capturing on its tile
+    ///
+    /// This is the static exchange evaluation (SEE). After the move, the sides take turns
+    /// capturing on the tile with their least valuable piece, and either side can stop when
+    /// capturing would lose material. Sliders behind other pieces join in when the piece in front
+    /// of them has captured. Checks and pins are ignored.
+    ///
+    /// # Arguments
+    /// * `mv` - The move to evaluate, usually a capture, it has to be legal in the position
+    ///
+    /// # Returns
+    /// * `i32` - The material gained by the current turn in centipawns, negative if it loses
+    ///   material
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// // The pawn on d5 is defended by the pawn on e6
+    /// let game = Game::from_fen("4k3/8/4p3/3p4/8/8/8/3QK3 w - -").unwrap();
+    /// assert_eq!(game.see(game.parse_san("Qxd5").unwrap()), 100 - 900);
+    /// ```
+    pub fn see(&self, mv: Move) -> i32 {
+        let (from_x, from_y) = mv.from();
+        let (to_x, to_y) = mv.to();
+        let to = to_y * 8 + to_x;
+
+        let mut occupied = self.board.occupied() & !(1 << (from_y * 8 + from_x));
+
+        // The gains of each capture in the sequence, from the perspective of the side capturing
+        let mut gains = Vec::with_capacity(32);
+
+        gains.push(match mv.capture() {
+            Some((c_x, c_y)) => {
+                // A pawn captured en passant doesn't stand on the tile that is moved to
+                occupied &= !(1 << (c_y * 8 + c_x));
+                self.board
+                    .get_tile(c_x, c_y)
+                    .map_or(0, |piece| piece.piece_type.value())
+            }
+            None => 0,
+        });
+
+        // The value of the piece that stands on the tile and can be captured next
+        let mut on_tile = match self.board.get_tile(from_x, from_y) {
+            Some(piece) => piece.piece_type.value(),
+            None => return 0,
+        };
+        if let Some(promotion) = mv.promotion() {
+            gains[0] += promotion.value() - PieceType::Pawn.value();
+            on_tile = promotion.value();
+        }
+
+        let mut side = self.turn.opposite();
+
+        loop {
+            let attackers = self.attackers_mask_with(to, side, occupied);
+
+            // The least valuable attacker captures first
+            let attacker = PieceType::all().into_iter().find_map(|piece_type| {
+                let pieces = attackers & self.board.pieces(piece_type, side);
+                (pieces != 0).then(|| (piece_type, pieces & pieces.wrapping_neg()))
+            });
+
+            let (piece_type, bit) = match attacker {
+                Some(attacker) => attacker,
+                None => break,
+            };
+
+            // The king can only capture when the tile isn't defended anymore
+            if piece_type == PieceType::King
+                && self.attackers_mask_with(to, side.opposite(), occupied & !bit) != 0
+            {
+                break;
+            }
+
+            gains.push(on_tile - gains[gains.len() - 1]);
+            occupied &= !bit;
+            on_tile = piece_type.value();
+            side = side.opposite();
+        }
+
+        // Go backwards through the sequence, each side stops capturing if it would lose material
+        for i in (1..gains.len()).rev() {
+            gains[i - 1] = -(-gains[i - 1]).max(gains[i]);
+        }
+
+        gains[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn see(fen: &str, san: &str) -> i32 {
+        let game = Game::from_fen(fen).unwrap();
+        game.see(game.parse_san(san).unwrap())
+    }
+
+    #[test]
+    pub fn see_of_simple_captures() {
+        // A pawn takes a queen defended by a pawn
+        assert_eq!(see("4k3/2p5/3q4/4P3/8/8/8/4K3 w - -", "exd6"), 900 - 100);
+        // A queen takes a pawn defended by a pawn
+        assert_eq!(see("4k3/8/4p3/3p4/8/8/8/3QK3 w - -", "Qxd5"), 100 - 900);
+        // An undefended pawn
+        assert_eq!(see("4k3/8/8/3p4/8/8/8/3QK3 w - -", "Qxd5"), 100);
+        // A quiet move to a tile attacked by a pawn loses the piece
+        assert_eq!(see("4k3/8/4p3/8/8/8/8/3QK3 w - -", "Qd5"), -900);
+    }
+
+    #[test]
+    pub fn see_with_x_rays() {
+        // The rook on d1 joins in after the rook on d2 has captured
+        assert_eq!(see("3rk3/8/8/3p4/8/8/3R4/3RK3 w - -", "Rxd5"), 100);
+        // Without the second rook the rook is lost
+        assert_eq!(see("3rk3/8/8/3p4/8/8/3R4/4K3 w - -", "Rxd5"), 100 - 500);
+        // The queen behind the bishop joins in along the diagonal
+        assert_eq!(
+            see("4k3/8/5p2/4p3/8/8/1B6/Q3K3 w - -", "Bxe5"),
+            100 - 330 + 100
+        );
+        assert_eq!(see("4k3/8/5p2/4p3/8/8/1B6/4K3 w - -", "Bxe5"), 100 - 330);
+        // Both queens stand behind the rooks, white stops before the queens are traded
+        assert_eq!(see("3qk3/3r4/8/3p4/8/8/3R4/3QK3 w - -", "Rxd5"), 100 - 500);
+    }
+
+    #[test]
+    pub fn king_cant_capture_defended_pieces() {
+        // The king can't recapture on d2 since the bishop on a5 defends the tile
+        assert_eq!(see("4k3/8/8/b7/8/8/8/3rK3 b - -", "Rd2"), 0);
+        assert_eq!(see("4k3/8/8/8/8/8/8/3rK3 b - -", "Rd2"), -500);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{coord, error::MoveError, Game, Move, PieceType};
+
+impl Game {
+    /// Parses a move in the UCI format for the current turn
+    ///
+    /// # Arguments
+    /// * `uci` - The move, the from and to tiles followed by the promotion, e.g `e2e4`, `e1g1` or
+    ///   `a7a8q`
+    ///
+    /// # Returns
+    /// * `Result<Move, MoveError>` - The legal move or an error if the move couldn't be parsed or
+    ///   isn't legal
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, Move};
+    ///
+    /// let game = Game::start_pos();
+    /// assert_eq!(
+    ///     game.parse_uci("g1f3").unwrap(),
+    ///     Move::Quiet { from: (6, 0), to: (5, 2) }
+    /// );
+    /// ```
+    pub fn parse_uci(&self, uci: &str) -> Result<Move, MoveError> {
+        let uci = uci.trim();
+
+        if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+            return Err(MoveError::parse_failed(
+                "A UCI move is 4 or 5 characters long",
+            ));
+        }
+
+        let square = |s| coord::from_algebraic(s).ok_or(MoveError::parse_failed("Invalid square"));
+        let from = square(&uci[0..2])?;
+        let to = square(&uci[2..4])?;
+        let promotion = match uci[4..].chars().next() {
+            Some(c) => match PieceType::from_fen_char(c.to_ascii_lowercase()) {
+                Some((piece_type, _)) if piece_type != PieceType::Pawn => Some(piece_type),
+                _ => return Err(MoveError::parse_failed("Invalid promotion piece")),
+            },
+            None => None,
+        };
+
+        self.find_move(from, to, promotion)
+    }
+
+    /// Internal helper that finds the legal move between two tiles
+    ///
+    /// # Returns
+    /// * `Result<Move, MoveError>` - The move, or an error telling why there is no such move
+    pub(super) fn find_move(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        promotion: Option<PieceType>,
+    ) -> Result<Move, MoveError> {
+        let piece = self
+            .board
+            .get_tile(from.0, from.1)
+            .ok_or(MoveError::NoPieceOnSource)?;
+
+        if piece.color != self.turn {
+            return Err(MoveError::WrongColorPiece);
+        }
+
+        let mut moves = self
+            .legal_moves_iter()
+            .filter(|m| m.from() == from && m.to() == to);
+
+        match moves.find(|m| m.promotion() == promotion) {
+            Some(mv) => Ok(mv),
+            None if promotion.is_none()
+                && self
+                    .legal_moves_iter()
+                    .any(|m| m.from() == from && m.to() == to && m.is_promotion()) =>
+            {
+                Err(MoveError::PromotionRequired)
+            }
+            None => Err(MoveError::Illegal),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn uci_round_trip() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq -",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+
+            for mv in game.legal_moves_iter() {
+                assert_eq!(game.parse_uci(&mv.to_uci()).unwrap(), mv);
+            }
+        }
+    }
+
+    #[test]
+    pub fn parse_uci_errors() {
+        let game = Game::start_pos();
+
+        assert!(matches!(game.parse_uci("e2e5"), Err(MoveError::Illegal)));
+        assert!(matches!(
+            game.parse_uci("e2"),
+            Err(MoveError::ParseFailed { .. })
+        ));
+        assert!(matches!(
+            game.parse_uci("e2e4x"),
+            Err(MoveError::ParseFailed { .. })
+        ));
+        assert!(matches!(
+            game.parse_uci("i2e4"),
+            Err(MoveError::ParseFailed { .. })
+        ));
+        assert!(matches!(
+            game.parse_uci("e4e5"),
+            Err(MoveError::NoPieceOnSource)
+        ));
+        assert!(matches!(
+            game.parse_uci("e7e5"),
+            Err(MoveError::WrongColorPiece)
+        ));
+
+        let game = Game::from_fen("4k3/P7/8/8/8/8/8/4K3 w - -").unwrap();
+        assert!(matches!(
+            game.parse_uci("a7a8"),
+            Err(MoveError::PromotionRequired)
+        ));
+        assert!(matches!(game.parse_uci("a7b8q"), Err(MoveError::Illegal)));
+        assert!(game.parse_uci("a7a8N").unwrap().is_promotion());
+    }
+}
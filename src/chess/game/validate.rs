@@ -0,0 +1,108 @@
+use crate::{error::MoveError, Game, Move};
+
+impl Game {
+    /// Applies a list of moves from a position and checks that every move is legal
+    ///
+    /// Useful before trusting a game from a PGN file or a user, where [`Game::apply_move`] would
+    /// happily apply illegal moves.
+    ///
+    /// # Arguments
+    /// * `start` - The position before the first move
+    /// * `moves` - The moves to apply, the first one for the turn of `start`
+    ///
+    /// # Returns
+    /// * `Result<Game, (usize, MoveError)>` - The position after the last move, or the index and
+    ///   error of the first move that isn't legal
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{error::MoveError, Game};
+    ///
+    /// let start = Game::start_pos();
+    /// let e4 = start.parse_uci("e2e4").unwrap();
+    ///
+    /// let game = Game::validate_line(&start, &[e4]).unwrap();
+    /// assert_eq!(game.fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3");
+    ///
+    /// assert!(matches!(
+    ///     Game::validate_line(&start, &[e4, e4]),
+    ///     Err((1, MoveError::NoPieceOnSource))
+    /// ));
+    /// ```
+    pub fn validate_line(start: &Game, moves: &[Move]) -> Result<Game, (usize, MoveError)> {
+        let mut game = *start;
+
+        for (i, mv) in moves.iter().enumerate() {
+            let legal = game
+                .find_move(mv.from(), mv.to(), mv.promotion())
+                .map_err(|err| (i, err))?;
+
+            // The tiles match a legal move, but the move itself could still be made up, like a
+            // capture of an empty tile
+            if legal != *mv {
+                return Err((i, MoveError::Illegal));
+            }
+
+            game.apply_move(legal)
+                .expect("Legal moves always start on a piece");
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    pub fn validate_line_finds_the_first_illegal_move() {
+        let start = Game::start_pos();
+
+        let mut moves = Vec::new();
+        let mut game = start;
+        for uci in ["e2e4", "e7e5", "g1f3", "b8c6"] {
+            let mv = game.parse_uci(uci).unwrap();
+            game.apply_move(mv).unwrap();
+            moves.push(mv);
+        }
+
+        assert_eq!(Game::validate_line(&start, &moves).unwrap(), game);
+        assert_eq!(Game::validate_line(&start, &[]).unwrap(), start);
+
+        // The rook on a1 can't jump over the pawn on a2
+        let mut illegal = moves.clone();
+        illegal.insert(
+            2,
+            Move::Quiet {
+                from: (0, 0),
+                to: (0, 2),
+            },
+        );
+        assert!(matches!(
+            Game::validate_line(&start, &illegal),
+            Err((2, MoveError::Illegal))
+        ));
+
+        // A move of the wrong color
+        let mut illegal = moves.clone();
+        illegal.swap(0, 1);
+        assert!(matches!(
+            Game::validate_line(&start, &illegal),
+            Err((0, MoveError::WrongColorPiece))
+        ));
+
+        // A legal move written as a capture
+        let mut illegal = moves;
+        illegal[2] = Move::Capture {
+            from: (6, 0),
+            to: (5, 2),
+            capture: (5, 2),
+        };
+        assert!(matches!(
+            Game::validate_line(&start, &illegal),
+            Err((2, MoveError::Illegal))
+        ));
+    }
+}
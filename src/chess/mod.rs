@@ -7,7 +7,44 @@ pub use game::*;
 mod board;
 pub use board::*;
 
+mod bitboard;
+pub use bitboard::*;
+
+mod pawn_structure;
+
 pub mod error;
 
+pub mod coord;
+
+pub mod chess960;
+
+#[cfg(feature = "std")]
+pub mod epd;
+
 mod mv;
 pub use mv::*;
+
+mod move_list;
+pub use move_list::*;
+
+#[cfg(feature = "std")]
+mod polyglot;
+#[cfg(feature = "std")]
+pub use polyglot::*;
+
+#[cfg(feature = "std")]
+pub mod pgn;
+
+#[cfg(feature = "std")]
+mod opening_book;
+#[cfg(feature = "std")]
+pub use opening_book::*;
+
+mod rng;
+pub use rng::*;
+
+mod search;
+pub use search::*;
+
+#[cfg(feature = "wasm-wrapper")]
+pub mod wasm;
@@ -0,0 +1,198 @@
+use alloc::vec::Vec;
+
+use crate::Move;
+
+/// The amount of moves a [`MoveList`] holds on the stack, no legal position has more than 218
+pub const MAX_MOVES: usize = 256;
+
+/// A list of moves stored on the stack
+///
+/// Generating moves into a [`MoveList`] doesn't allocate, so the same list can be cleared and
+/// refilled at every node of a search. Made up positions, e.g with 20 queens, can have more than
+/// [`MAX_MOVES`] moves, then the moves are moved to the heap instead.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Game, MoveList};
+///
+/// let game = Game::start_pos();
+/// let mut moves = MoveList::new();
+///
+/// game.gen_all_moves_into(&mut moves);
+/// assert_eq!(moves.len(), 20);
+/// ```
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+    /// All the moves once there has been more than [`MAX_MOVES`], empty until then
+    spilled: Vec<Move>,
+}
+
+impl MoveList {
+    /// Creates an empty move list
+    pub fn new() -> MoveList {
+        MoveList {
+            // Filler that is never read, only the first `len` moves are used
+            moves: [Move::Quiet {
+                from: (0, 0),
+                to: (0, 0),
+            }; MAX_MOVES],
+            len: 0,
+            spilled: Vec::new(),
+        }
+    }
+
+    /// Adds a move to the end of the list
+    ///
+    /// The first [`MAX_MOVES`] moves are stored on the stack, any more than that moves all of
+    /// them to the heap.
+    pub fn push(&mut self, mv: Move) {
+        if self.spilled.is_empty() && self.len < MAX_MOVES {
+            self.moves[self.len] = mv;
+        } else {
+            if self.spilled.is_empty() {
+                self.spilled.extend_from_slice(&self.moves);
+            }
+            self.spilled.push(mv);
+        }
+
+        self.len += 1;
+    }
+
+    /// Shortens the list to a certain length, nothing happens if the list is already shorter
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+        self.spilled.truncate(len);
+    }
+
+    /// Removes all moves from the list
+    ///
+    /// The heap memory of a spilled list is kept, but new moves are stored on the stack again.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.spilled.clear();
+    }
+
+    /// Returns the moves as a slice
+    pub fn as_slice(&self) -> &[Move] {
+        if self.spilled.is_empty() {
+            &self.moves[..self.len]
+        } else {
+            &self.spilled
+        }
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        MoveList::new()
+    }
+}
+
+impl core::ops::Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl core::ops::DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if self.spilled.is_empty() {
+            &mut self.moves[..self.len]
+        } else {
+            &mut self.spilled
+        }
+    }
+}
+
+impl core::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for MoveList {}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = core::iter::Chain<
+        core::iter::Take<core::array::IntoIter<Move, MAX_MOVES>>,
+        alloc::vec::IntoIter<Move>,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // Only one of the parts has moves, the heap once the list has spilled
+        let on_stack = if self.spilled.is_empty() { self.len } else { 0 };
+
+        self.moves.into_iter().take(on_stack).chain(self.spilled)
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = core::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn push_and_clear() {
+        let mut moves = MoveList::new();
+        assert!(moves.is_empty());
+
+        let mv = Move::Quiet {
+            from: (4, 1),
+            to: (4, 2),
+        };
+        moves.push(mv);
+        moves.push(mv);
+
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[1], mv);
+        assert_eq!(moves.clone().into_iter().collect::<Vec<_>>(), vec![mv, mv]);
+
+        moves.clear();
+        assert!(moves.is_empty());
+        assert_eq!(moves.clone().into_iter().count(), 0);
+    }
+
+    #[test]
+    pub fn more_than_max_moves_spill_to_the_heap() {
+        let mut moves = MoveList::new();
+        let mv = |i: usize| Move::Quiet {
+            from: (i % 8, (i / 8) % 8),
+            to: (0, 0),
+        };
+
+        for i in 0..MAX_MOVES + 10 {
+            moves.push(mv(i));
+        }
+
+        assert_eq!(moves.len(), MAX_MOVES + 10);
+        assert_eq!(moves[MAX_MOVES - 1], mv(MAX_MOVES - 1));
+        assert_eq!(moves[MAX_MOVES + 9], mv(MAX_MOVES + 9));
+        assert_eq!(moves.clone().into_iter().count(), MAX_MOVES + 10);
+
+        moves.truncate(3);
+        assert_eq!(moves.as_slice(), &[mv(0), mv(1), mv(2)]);
+
+        moves.clear();
+        moves.push(mv(5));
+        assert_eq!(moves.into_iter().collect::<Vec<_>>(), vec![mv(5)]);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::PieceType;
+use alloc::string::String;
+
+use crate::{coord, Color, PieceType};
 
 /// A move that can be applied to a game
 ///
@@ -7,7 +9,7 @@ use crate::PieceType;
 ///
 /// A good way to render a move is to check `from()` and `to()` first, if you need to render the
 /// capture square you can use `capture()`.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Move {
     /// A move that is not a capture
     Quiet {
@@ -49,24 +51,15 @@ pub enum Move {
 
 impl Move {
     pub fn is_double_pawn_push(&self) -> bool {
-        match self {
-            Move::DoublePawnPush { .. } => true,
-            _ => false,
-        }
+        matches!(self, Move::DoublePawnPush { .. })
     }
 
     pub fn is_capture(&self) -> bool {
-        match self {
-            Move::Capture { .. } | Move::CapturePromotion { .. } => true,
-            _ => false,
-        }
+        matches!(self, Move::Capture { .. } | Move::CapturePromotion { .. })
     }
 
     pub fn is_castle(&self) -> bool {
-        match self {
-            Move::Castle { .. } => true,
-            _ => false,
-        }
+        matches!(self, Move::Castle { .. })
     }
 
     pub fn is_queen_side_castle(&self) -> bool {
@@ -84,10 +77,10 @@ impl Move {
     }
 
     pub fn is_promotion(&self) -> bool {
-        match self {
-            Move::QuietPromotion { .. } | Move::CapturePromotion { .. } => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            Move::QuietPromotion { .. } | Move::CapturePromotion { .. }
+        )
     }
 
     /// Returns the move from square. If the move is a castle, it returns the king square
@@ -147,4 +140,26 @@ impl Move {
             _ => None,
         }
     }
+
+    /// Returns the move in the UCI format, the from and to tiles followed by the promotion
+    ///
+    /// Castling is written as the move of the king, e.g `e1g1`.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Move, PieceType};
+    ///
+    /// let mv = Move::QuietPromotion { from: (0, 6), to: (0, 7), promotion: PieceType::Queen };
+    /// assert_eq!(mv.to_uci(), "a7a8q");
+    /// ```
+    pub fn to_uci(&self) -> String {
+        let mut uci = coord::to_algebraic(self.from());
+        uci.push_str(&coord::to_algebraic(self.to()));
+
+        if let Some(promotion) = self.promotion() {
+            uci.push(promotion.fen_char(Color::Black));
+        }
+
+        uci
+    }
 }
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use crate::{pgn::PgnGame, Game, Move, Rng};
+
+/// An opening book built from a collection of games
+///
+/// The book counts how many times each move has been played in each position, positions are
+/// keyed by their Polyglot key so transpositions end up in the same entry.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{pgn, Game, OpeningBook};
+///
+/// let games = pgn::parse_pgn("1. e4 e5 1-0\n\n1. e4 c5 0-1\n\n1. d4 d5 1/2-1/2").unwrap();
+/// let book = OpeningBook::from_pgn(&games, 10);
+///
+/// let game = Game::start_pos();
+/// assert_eq!(book.best(&game), Some(game.parse_san("e4").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OpeningBook {
+    positions: HashMap<u64, Vec<(Move, u32)>>,
+}
+
+impl OpeningBook {
+    /// Builds a book from games
+    ///
+    /// Games that start from a custom position are included from that position. If a game
+    /// contains a move that can't be played, the rest of that game is skipped.
+    ///
+    /// # Arguments
+    /// * `games` - The games to build the book from
+    /// * `max_ply` - How many half moves into each game to include
+    pub fn from_pgn(games: &[PgnGame], max_ply: usize) -> OpeningBook {
+        let mut book = OpeningBook::default();
+
+        for pgn_game in games {
+            let mut game = match pgn_game.start_position() {
+                Ok(game) => game,
+                Err(_) => continue,
+            };
+
+            for san in pgn_game.moves.iter().take(max_ply) {
+                let mv = match game.parse_san(san) {
+                    Ok(mv) => mv,
+                    Err(_) => break,
+                };
+
+                book.add(&game, mv);
+
+                game.apply_move(mv)
+                    .expect("parse_san only returns legal moves");
+            }
+        }
+
+        book
+    }
+
+    /// Internal helper that counts a move played in a position
+    fn add(&mut self, game: &Game, mv: Move) {
+        let moves = self.positions.entry(game.polyglot_key()).or_default();
+
+        match moves.iter_mut().find(|(m, _)| *m == mv) {
+            Some((_, count)) => *count += 1,
+            None => moves.push((mv, 1)),
+        }
+    }
+
+    /// Returns the book moves for a position
+    ///
+    /// # Returns
+    /// * `Vec<(Move, u32)>` - The moves together with how many times they were played, in the
+    ///   order they were first seen
+    pub fn moves(&self, game: &Game) -> Vec<(Move, u32)> {
+        self.positions
+            .get(&game.polyglot_key())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the most played move in a position
+    ///
+    /// If several moves are played equally often, the one that was seen first is returned.
+    ///
+    /// # Returns
+    /// * `Option<Move>` - The most played move or None if the position isn't in the book
+    pub fn best(&self, game: &Game) -> Option<Move> {
+        let mut best: Option<(Move, u32)> = None;
+
+        for (mv, count) in self.moves(game) {
+            if best.is_none_or(|(_, best_count)| count > best_count) {
+                best = Some((mv, count));
+            }
+        }
+
+        best.map(|(mv, _)| mv)
+    }
+
+    /// Returns a random book move, where moves that were played more often are more likely to be
+    /// picked
+    ///
+    /// # Arguments
+    /// * `game` - The position to pick a move for
+    /// * `rng` - The random number generator to use
+    ///
+    /// # Returns
+    /// * `Option<Move>` - The picked move or None if the position isn't in the book
+    pub fn weighted_random<R: Rng>(&self, game: &Game, rng: &mut R) -> Option<Move> {
+        let moves = self.moves(game);
+        let total = moves.iter().map(|(_, count)| *count as u64).sum::<u64>();
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.gen_below(total);
+
+        for (mv, count) in moves {
+            if pick < count as u64 {
+                return Some(mv);
+            }
+            pick -= count as u64;
+        }
+
+        unreachable!("The pick is always below the total count")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pgn, XorShiftRng};
+
+    #[test]
+    pub fn shared_moves_are_counted() {
+        let games =
+            pgn::parse_pgn("1. e4 e5 2. Nf3 Nc6 3. Bb5 1-0\n\n1. e4 e5 2. Nf3 Nf6 3. Nxe5 0-1")
+                .unwrap();
+
+        let book = OpeningBook::from_pgn(&games, 10);
+
+        let mut game = Game::start_pos();
+        for san in ["e4", "e5"] {
+            let mv = game.parse_san(san).unwrap();
+            assert_eq!(book.moves(&game), vec![(mv, 2)]);
+            game.apply_move(mv).unwrap();
+        }
+
+        let nf3 = game.parse_san("Nf3").unwrap();
+        assert_eq!(book.moves(&game), vec![(nf3, 2)]);
+        assert_eq!(book.best(&game), Some(nf3));
+        game.apply_move(nf3).unwrap();
+
+        let moves = book.moves(&game);
+        assert_eq!(moves.len(), 2);
+        assert!(moves.iter().all(|(_, count)| *count == 1));
+        assert_eq!(book.best(&game), Some(game.parse_san("Nc6").unwrap()));
+    }
+
+    #[test]
+    pub fn max_ply_limits_the_book() {
+        let games = pgn::parse_pgn("1. e4 e5 2. Nf3 Nc6 1-0").unwrap();
+        let book = OpeningBook::from_pgn(&games, 1);
+
+        let mut game = Game::start_pos();
+        assert!(book.best(&game).is_some());
+
+        game.apply_move(game.parse_san("e4").unwrap()).unwrap();
+        assert!(book.best(&game).is_none());
+    }
+
+    #[test]
+    pub fn weighted_random_picks_book_moves() {
+        let games = pgn::parse_pgn("1. e4 1-0\n\n1. e4 1-0\n\n1. d4 1-0").unwrap();
+        let book = OpeningBook::from_pgn(&games, 10);
+
+        let game = Game::start_pos();
+        let e4 = game.parse_san("e4").unwrap();
+        let d4 = game.parse_san("d4").unwrap();
+
+        let mut rng = XorShiftRng::new(7);
+        let picks = (0..300)
+            .map(|_| book.weighted_random(&game, &mut rng).unwrap())
+            .collect::<Vec<Move>>();
+
+        assert!(picks.iter().all(|m| *m == e4 || *m == d4));
+        let e4_picks = picks.iter().filter(|m| **m == e4).count();
+        assert!(e4_picks > 150 && e4_picks < 250);
+
+        let empty = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+        assert!(book.weighted_random(&empty, &mut rng).is_none());
+    }
+}
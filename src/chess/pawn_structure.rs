@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+
+use crate::{Bitboard, Board, Color, PieceType};
+
+/// The tiles of the a file, shift left by x for the other files
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+impl Board {
+    /// Returns how many pawns of a color share a file with another pawn of the same color
+    ///
+    /// Every pawn on the file is counted, so a doubled pair counts as two pawns.
+    ///
+    /// # Arguments
+    /// * `color` - The color of the pawns
+    ///
+    /// # Returns
+    /// * `u8` - The amount of doubled pawns
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Board, Color};
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/2P5/2P5/4K3").unwrap();
+    /// assert_eq!(board.doubled_pawns(Color::White), 2);
+    /// ```
+    pub fn doubled_pawns(&self, color: Color) -> u8 {
+        let pawns = self.pieces(PieceType::Pawn, color);
+
+        self.pawns_where(color, |x, _| (pawns & FILE_A << x).count_ones() > 1)
+            .len() as u8
+    }
+
+    /// Returns how many pawns of a color have no pawns of the same color on the files next to
+    /// them
+    ///
+    /// # Arguments
+    /// * `color` - The color of the pawns
+    ///
+    /// # Returns
+    /// * `u8` - The amount of isolated pawns
+    pub fn isolated_pawns(&self, color: Color) -> u8 {
+        let pawns = self.pieces(PieceType::Pawn, color);
+
+        self.pawns_where(color, |x, _| pawns & neighbour_files(x) == 0)
+            .len() as u8
+    }
+
+    /// Returns the pawns of a color that have no enemy pawns in front of them on their own file or
+    /// the files next to them
+    ///
+    /// # Arguments
+    /// * `color` - The color of the pawns
+    ///
+    /// # Returns
+    /// * `Vec<(usize, usize)>` - The positions of the pawns
+    pub fn passed_pawns(&self, color: Color) -> Vec<(usize, usize)> {
+        let enemy_pawns = self.pieces(PieceType::Pawn, color.opposite());
+
+        self.pawns_where(color, |x, y| {
+            // White pawns move towards y = 7, so the tiles in front of them have a higher index
+            let in_front = match color {
+                Color::White if y == 7 => 0,
+                Color::White => !0u64 << ((y + 1) * 8),
+                Color::Black => (1u64 << (y * 8)) - 1,
+            };
+
+            enemy_pawns & in_front & (FILE_A << x | neighbour_files(x)) == 0
+        })
+    }
+
+    /// Internal helper that returns the pawns of a color that fulfill a condition
+    fn pawns_where<F>(&self, color: Color, condition: F) -> Vec<(usize, usize)>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        Bitboard(self.pieces(PieceType::Pawn, color))
+            .squares()
+            .filter(|(x, y)| condition(*x, *y))
+            .collect()
+    }
+}
+
+/// Internal helper that returns the files next to a file, the a and h files only have one
+fn neighbour_files(x: usize) -> u64 {
+    let left = if x > 0 { FILE_A << (x - 1) } else { 0 };
+    let right = if x < 7 { FILE_A << (x + 1) } else { 0 };
+
+    left | right
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn doubled_and_isolated_pawns() {
+        // White has doubled pawns on the c file and an isolated pawn on the h file, black has an
+        // isolated pawn on the a file
+        let board = Board::from_fen("4k3/p4pp1/8/8/2P5/2P4P/1P6/4K3").unwrap();
+
+        assert_eq!(board.doubled_pawns(Color::White), 2);
+        assert_eq!(board.doubled_pawns(Color::Black), 0);
+
+        assert_eq!(board.isolated_pawns(Color::White), 1);
+        assert_eq!(board.isolated_pawns(Color::Black), 1);
+
+        // All three pawns on the d file are doubled and isolated, the pawns on the g and h file
+        // have each other as neighbours
+        let board = Board::from_fen("4k3/8/8/8/3P4/3P4/3P2PP/4K3").unwrap();
+        assert_eq!(board.doubled_pawns(Color::White), 3);
+        assert_eq!(board.isolated_pawns(Color::White), 3);
+    }
+
+    #[test]
+    pub fn passed_pawns() {
+        // The white pawn on a5 is passed, the pawn on e4 is stopped by the pawn on f5
+        // The black pawn on h3 is passed, the pawn on f5 is stopped by the pawn on e4
+        let board = Board::from_fen("4k3/8/8/P4p2/4P3/7p/8/4K3").unwrap();
+
+        assert_eq!(board.passed_pawns(Color::White), vec![(0, 4)]);
+        assert_eq!(board.passed_pawns(Color::Black), vec![(7, 2)]);
+
+        // Pawns next to each other on the same rank don't stop each other
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3").unwrap();
+        assert_eq!(board.passed_pawns(Color::White), vec![(4, 4)]);
+        assert_eq!(board.passed_pawns(Color::Black), vec![(3, 4)]);
+
+        // Pawns that have passed each other only have enemy pawns behind them
+        let board = Board::from_fen("4k3/8/8/3P4/4p3/8/8/4K3").unwrap();
+        assert_eq!(board.passed_pawns(Color::White), vec![(3, 4)]);
+        assert_eq!(board.passed_pawns(Color::Black), vec![(4, 3)]);
+
+        // Pawns about to promote, every pawn has the enemy pawn next to it behind it
+        let board = Board::from_fen("4k3/P7/1p6/8/8/6P1/7p/4K3").unwrap();
+        assert_eq!(board.passed_pawns(Color::White), vec![(6, 2), (0, 6)]);
+        assert_eq!(board.passed_pawns(Color::Black), vec![(7, 1), (1, 5)]);
+    }
+}
@@ -0,0 +1,482 @@
+//! Parsing of PGN (Portable Game Notation) files
+//!
+//! A PGN file contains one or more games, each game is a list of tags like `[White "Fritiof"]`
+//! followed by the moves in SAN. Comments, variations and annotations are skipped when parsing.
+//!
+//! Use [`parse_pgn`] for a PGN that fits in memory and [`PgnReader`] to read large databases one
+//! game at a time. [`format_movetext`] writes moves the other way around.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use crate::{
+    error::{MoveError, PgnError},
+    Color, Game, Move,
+};
+
+/// A game read from a PGN file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgnGame {
+    /// The tags of the game in the order they appear, e.g `("White", "Fritiof")`
+    pub tags: Vec<(String, String)>,
+    /// The moves of the game in SAN
+    pub moves: Vec<String>,
+    /// The result of the game, `1-0`, `0-1`, `1/2-1/2` or `*`
+    pub result: Option<String>,
+}
+
+impl PgnGame {
+    /// Returns the value of a tag
+    ///
+    /// # Arguments
+    /// * `name` - The name of the tag, e.g `White`
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the position the game starts from
+    ///
+    /// This is the position in the `FEN` tag if there is one, otherwise the starting position.
+    pub fn start_position(&self) -> Result<Game, PgnError> {
+        match self.tag("FEN") {
+            Some(fen) => Ok(Game::from_fen(fen)?),
+            None => Ok(Game::start_pos()),
+        }
+    }
+}
+
+/// Parses all games in a PGN string
+///
+/// # Arguments
+/// * `pgn` - The content of a PGN file
+///
+/// # Returns
+/// * `Result<Vec<PgnGame>, PgnError>` - The games or an error if the PGN is malformed
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::pgn;
+///
+/// let games = pgn::parse_pgn("[White \"Fritiof\"]\n\n1. e4 e5 2. Nf3 {Good move} Nc6 1-0").unwrap();
+///
+/// assert_eq!(games[0].tag("White"), Some("Fritiof"));
+/// assert_eq!(games[0].moves, vec!["e4", "e5", "Nf3", "Nc6"]);
+/// ```
+pub fn parse_pgn(pgn: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut games = vec![];
+    let mut game = PgnGame::default();
+    // Set when the moves of the game has started, a tag after that belongs to the next game
+    let mut in_movetext = false;
+
+    let mut chars = pgn.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                if in_movetext {
+                    games.push(std::mem::take(&mut game));
+                    in_movetext = false;
+                }
+
+                let mut tag = String::new();
+                let mut in_string = false;
+                loop {
+                    match chars.next() {
+                        Some('\\') if in_string => {
+                            if let Some(c) = chars.next() {
+                                tag.push(c);
+                            }
+                        }
+                        Some('"') => {
+                            in_string = !in_string;
+                            tag.push('"');
+                        }
+                        Some(']') if !in_string => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(PgnError::UnterminatedTag),
+                    }
+                }
+
+                game.tags.push(parse_tag(&tag)?);
+            }
+            '{' => {
+                if !chars.by_ref().any(|c| c == '}') {
+                    return Err(PgnError::UnterminatedComment);
+                }
+            }
+            ';' => {
+                chars.by_ref().find(|c| *c == '\n');
+            }
+            '(' => {
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next() {
+                        Some('(') => depth += 1,
+                        Some(')') => depth -= 1,
+                        Some('{') => {
+                            if !chars.by_ref().any(|c| c == '}') {
+                                return Err(PgnError::UnterminatedComment);
+                            }
+                        }
+                        Some(_) => (),
+                        None => return Err(PgnError::UnterminatedVariation),
+                    }
+                }
+            }
+            c if c.is_whitespace() => (),
+            c => {
+                let mut token = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !"[]{}();".contains(*c))
+                {
+                    token.push(c);
+                }
+
+                match token.as_str() {
+                    // The result marks the end of the game
+                    "1-0" | "0-1" | "1/2-1/2" | "*" => {
+                        game.result = Some(token);
+                        games.push(std::mem::take(&mut game));
+                        in_movetext = false;
+                    }
+                    t if t.starts_with('$') => (),
+                    t => {
+                        in_movetext = true;
+
+                        let san = strip_move_number(t);
+                        if !san.is_empty() {
+                            game.moves.push(san.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if in_movetext || !game.tags.is_empty() {
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+/// Reads the games of a PGN one at a time
+///
+/// Only one game is kept in memory at a time, so this can be used on databases that are too
+/// large to load at once. A new game starts at a tag that follows the moves of the previous game
+/// and a blank line. If a game is malformed the error is returned and the reader continues with
+/// the next game.
+///
+/// # Examples
+/// ```
+/// use std::io::Cursor;
+/// use fritiofr_chess::pgn::PgnReader;
+///
+/// let pgn = "[White \"A\"]\n\n1. e4 e5 1-0\n\n[White \"B\"]\n\n1. d4 d5 0-1\n";
+/// let games = PgnReader::new(Cursor::new(pgn))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(games.len(), 2);
+/// assert_eq!(games[1].tag("White"), Some("B"));
+/// ```
+pub struct PgnReader<R: BufRead> {
+    reader: R,
+    /// The first line of the next game, read while looking for the end of the previous game
+    next_game: String,
+    /// Games that have been parsed but not returned yet, a part without blank lines between the
+    /// games can hold more than one
+    parsed: VecDeque<PgnGame>,
+    done: bool,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    /// Creates a reader that reads games from a buffered reader
+    ///
+    /// # Arguments
+    /// * `reader` - The PGN source, e.g a `BufReader` around a file
+    pub fn new(reader: R) -> PgnReader<R> {
+        PgnReader {
+            reader,
+            next_game: String::new(),
+            parsed: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Internal helper that reads the text of the next game, None when the reader is empty
+    fn read_game_text(&mut self) -> Result<Option<String>, std::io::Error> {
+        let mut text = std::mem::take(&mut self.next_game);
+        let mut in_movetext = false;
+        let mut after_blank_line = false;
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(if text.trim().is_empty() {
+                    None
+                } else {
+                    Some(text)
+                });
+            }
+
+            let trimmed = line.trim();
+
+            if trimmed.starts_with('[') && in_movetext && after_blank_line {
+                self.next_game = line;
+                return Ok(Some(text));
+            }
+
+            after_blank_line = trimmed.is_empty();
+            if !trimmed.is_empty() && !trimmed.starts_with('[') {
+                in_movetext = true;
+            }
+
+            text.push_str(&line);
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(game) = self.parsed.pop_front() {
+                return Some(Ok(game));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let text = match self.read_game_text() {
+                Ok(Some(text)) => text,
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(err) => {
+                    // The reader is likely broken, stop instead of returning the same error forever
+                    self.done = true;
+                    return Some(Err(err.into()));
+                }
+            };
+
+            match parse_pgn(&text) {
+                Ok(games) => self.parsed.extend(games),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// Writes moves as PGN movetext, SAN with move numbers and without tags or a result
+///
+/// If the game starts with black to move the first move is numbered like `1... e5`.
+///
+/// # Arguments
+/// * `start` - The position before the first move
+/// * `moves` - The moves, each one must be legal after the ones before it
+///
+/// # Returns
+/// * `Result<String, MoveError>` - The movetext or an error if a move isn't legal
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{pgn, Game};
+///
+/// let mut game = Game::start_pos();
+/// let mut moves = vec![];
+/// for san in ["e4", "e5", "Nf3", "Nc6"] {
+///     let mv = game.parse_san(san).unwrap();
+///     game.apply_move(mv).unwrap();
+///     moves.push(mv);
+/// }
+///
+/// assert_eq!(
+///     pgn::format_movetext(&Game::start_pos(), &moves).unwrap(),
+///     "1. e4 e5 2. Nf3 Nc6"
+/// );
+/// ```
+pub fn format_movetext(start: &Game, moves: &[Move]) -> Result<String, MoveError> {
+    let mut game = *start;
+    let mut tokens = vec![];
+
+    for (i, mv) in moves.iter().enumerate() {
+        match game.turn() {
+            Color::White => tokens.push(format!("{}.", game.fullmove_number())),
+            Color::Black if i == 0 => tokens.push(format!("{}...", game.fullmove_number())),
+            Color::Black => {}
+        }
+
+        tokens.push(game.to_san(*mv)?);
+        game.apply_move(*mv)
+            .expect("to_san only succeeds for legal moves");
+    }
+
+    Ok(tokens.join(" "))
+}
+
+/// Internal helper that strips a move number like `1.` or `12...` from the start of a token
+fn strip_move_number(token: &str) -> &str {
+    let digits = token.chars().take_while(|c| c.is_ascii_digit()).count();
+
+    if digits > 0 && token[digits..].starts_with('.') {
+        token[digits..].trim_start_matches('.')
+    } else {
+        token
+    }
+}
+
+/// Internal helper that parses the inside of a tag, e.g `White "Fritiof"`
+fn parse_tag(tag: &str) -> Result<(String, String), PgnError> {
+    let tag = tag.trim();
+
+    let (name, value) = tag
+        .split_once(char::is_whitespace)
+        .ok_or(PgnError::InvalidTag)?;
+    let value = value.trim();
+
+    if name.is_empty() || value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+        return Err(PgnError::InvalidTag);
+    }
+
+    Ok((name.to_string(), value[1..value.len() - 1].to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_multiple_games() {
+        let pgn = r#"[Event "Test"]
+[White "Fritiof \"The Engine\""]
+[Black "Someone"]
+
+1. e4 e5 2. Nf3 {A comment} Nc6 (2... d6 3. d4) 3. Bb5 $1 a6 1-0
+
+[Event "Test 2"]
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 w - -"]
+
+1. e4 Kd7 ; a line comment
+2. Kd2 1/2-1/2
+"#;
+
+        let games = parse_pgn(pgn).unwrap();
+        assert_eq!(games.len(), 2);
+
+        assert_eq!(games[0].tag("White"), Some("Fritiof \"The Engine\""));
+        assert_eq!(games[0].moves, vec!["e4", "e5", "Nf3", "Nc6", "Bb5", "a6"]);
+        assert_eq!(games[0].result.as_deref(), Some("1-0"));
+
+        assert_eq!(games[1].moves, vec!["e4", "Kd7", "Kd2"]);
+        assert_eq!(
+            games[1].start_position().unwrap().fen(),
+            "4k3/8/8/8/8/8/4P3/4K3 w - -"
+        );
+    }
+
+    #[test]
+    pub fn parse_malformed_pgn() {
+        assert!(matches!(
+            parse_pgn("[Event \"Test"),
+            Err(PgnError::UnterminatedTag)
+        ));
+        assert!(matches!(parse_pgn("[Event]"), Err(PgnError::InvalidTag)));
+        assert!(matches!(
+            parse_pgn("1. e4 {comment"),
+            Err(PgnError::UnterminatedComment)
+        ));
+        assert!(matches!(
+            parse_pgn("1. e4 (1. d4"),
+            Err(PgnError::UnterminatedVariation)
+        ));
+    }
+
+    #[test]
+    pub fn read_games_one_at_a_time() {
+        let pgn = r#"[Event "First"]
+
+1. e4 e5 2. Nf3 Nc6 1-0
+
+[Event "Broken"]
+
+1. d4 {This comment never ends
+2. c4 e6
+
+[Event "Third"]
+[Result "*"]
+
+1. c4 *
+"#;
+
+        let mut reader = PgnReader::new(std::io::Cursor::new(pgn));
+
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(first.tag("Event"), Some("First"));
+        assert_eq!(first.moves, vec!["e4", "e5", "Nf3", "Nc6"]);
+
+        assert!(matches!(
+            reader.next(),
+            Some(Err(PgnError::UnterminatedComment))
+        ));
+
+        let third = reader.next().unwrap().unwrap();
+        assert_eq!(third.tag("Event"), Some("Third"));
+        assert_eq!(third.moves, vec!["c4"]);
+        assert_eq!(third.result.as_deref(), Some("*"));
+
+        assert!(reader.next().is_none());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    pub fn format_movetext_from_both_colors() {
+        let play = |fen: &str, sans: &[&str]| {
+            let start = Game::from_fen(fen).unwrap();
+            let mut game = start;
+            let mut moves = vec![];
+
+            for san in sans {
+                let mv = game.parse_san(san).unwrap();
+                game.apply_move(mv).unwrap();
+                moves.push(mv);
+            }
+
+            format_movetext(&start, &moves).unwrap()
+        };
+
+        assert_eq!(
+            play(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                &["f3", "e5", "g4", "Qh4"]
+            ),
+            "1. f3 e5 2. g4 Qh4#"
+        );
+        assert_eq!(
+            play(
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+                &["e5", "Nf3", "Nc6"]
+            ),
+            "1... e5 2. Nf3 Nc6"
+        );
+        assert_eq!(play("4k3/8/8/8/8/8/8/4K2R w K - 0 40", &[]), "");
+
+        let start = Game::start_pos();
+        let illegal = Move::Quiet {
+            from: (4, 1),
+            to: (4, 4),
+        };
+        assert!(matches!(
+            format_movetext(&start, &[illegal]),
+            Err(MoveError::Illegal)
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use core::str::FromStr;
 
 use crate::error::ParsePieceError;
 
@@ -10,7 +10,7 @@ pub struct Piece {
 }
 
 /// A type of piece
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum PieceType {
     Pawn,
     Knight,
@@ -21,12 +21,34 @@ pub enum PieceType {
 }
 
 /// Either white or black
-#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Color {
     White,
     Black,
 }
 
+impl Piece {
+    /// Returns the FEN letter of the piece, uppercase for white and lowercase for black
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Piece, PieceType};
+    ///
+    /// let piece = Piece { piece_type: PieceType::Queen, color: Color::Black };
+    /// assert_eq!(piece.fen_char(), 'q');
+    /// ```
+    pub fn fen_char(&self) -> char {
+        self.piece_type.fen_char(self.color)
+    }
+
+    /// Returns the FEN letter of the piece, the same as [`Piece::fen_char`]
+    ///
+    /// The color of the piece decides the case, uppercase for white and lowercase for black.
+    pub fn to_fen_char(&self) -> char {
+        self.fen_char()
+    }
+}
+
 impl Color {
     /// Returns the opposite color
     ///
@@ -38,11 +60,67 @@ impl Color {
             Color::Black => Color::White,
         }
     }
+
+    /// Returns if the color is white
+    pub fn is_white(&self) -> bool {
+        *self == Color::White
+    }
 }
 
-impl Into<char> for Piece {
-    fn into(self) -> char {
-        let piece_char = match self.piece_type {
+impl core::ops::Not for Color {
+    type Output = Color;
+
+    fn not(self) -> Self::Output {
+        self.opposite()
+    }
+}
+
+impl PieceType {
+    /// Returns all piece types, from pawn to king
+    pub fn all() -> [PieceType; 6] {
+        [
+            PieceType::Pawn,
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Rook,
+            PieceType::Queen,
+            PieceType::King,
+        ]
+    }
+
+    /// Returns the material value of the piece type in centipawns
+    ///
+    /// The king is worth 0, since it can never be captured or traded.
+    pub fn value(&self) -> i32 {
+        match self {
+            PieceType::Pawn => 100,
+            PieceType::Knight => 320,
+            PieceType::Bishop => 330,
+            PieceType::Rook => 500,
+            PieceType::Queen => 900,
+            PieceType::King => 0,
+        }
+    }
+
+    /// Returns if the piece moves along rays, i.e bishops, rooks and queens
+    pub fn is_slider(&self) -> bool {
+        matches!(self, PieceType::Bishop | PieceType::Rook | PieceType::Queen)
+    }
+
+    /// Returns the FEN letter of the piece type
+    ///
+    /// # Arguments
+    /// * `color` - The color of the piece, white pieces are uppercase and black lowercase
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, PieceType};
+    ///
+    /// assert_eq!(PieceType::Knight.fen_char(Color::White), 'N');
+    /// assert_eq!(PieceType::Knight.fen_char(Color::Black), 'n');
+    /// ```
+    pub fn fen_char(&self, color: Color) -> char {
+        let piece_char = match self {
             PieceType::Pawn => 'p',
             PieceType::Knight => 'n',
             PieceType::Bishop => 'b',
@@ -51,34 +129,75 @@ impl Into<char> for Piece {
             PieceType::King => 'k',
         };
 
-        if self.color == Color::White {
+        if color.is_white() {
             piece_char.to_ascii_uppercase()
         } else {
             piece_char
         }
     }
-}
-
-impl TryFrom<char> for Piece {
-    type Error = ParsePieceError;
 
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        let piece_type = match value.to_ascii_lowercase() {
+    /// Parses a FEN letter
+    ///
+    /// # Arguments
+    /// * `c` - The letter, uppercase for white and lowercase for black
+    ///
+    /// # Returns
+    /// * `Option<(PieceType, Color)>` - The piece type and color or None if the letter isn't a
+    ///   piece
+    pub fn from_fen_char(c: char) -> Option<(PieceType, Color)> {
+        let piece_type = match c.to_ascii_lowercase() {
             'p' => PieceType::Pawn,
             'n' => PieceType::Knight,
             'b' => PieceType::Bishop,
             'r' => PieceType::Rook,
             'q' => PieceType::Queen,
             'k' => PieceType::King,
-            _ => return Err(ParsePieceError::UnknownCharacterPiece),
+            _ => return None,
         };
 
-        let color = if value.is_ascii_uppercase() {
+        let color = if c.is_ascii_uppercase() {
             Color::White
         } else {
             Color::Black
         };
 
+        Some((piece_type, color))
+    }
+
+    /// Returns the letter of the piece type, the same as [`PieceType::fen_char`]
+    ///
+    /// # Arguments
+    /// * `color` - The color of the piece, white pieces are uppercase and black lowercase
+    pub fn to_char(&self, color: Color) -> char {
+        self.fen_char(color)
+    }
+
+    /// Parses the letter of a piece, the same as [`PieceType::from_fen_char`]
+    ///
+    /// # Arguments
+    /// * `c` - The letter, uppercase for white and lowercase for black
+    ///
+    /// # Returns
+    /// * `Option<(PieceType, Color)>` - The piece type and color or None if the letter isn't a
+    ///   piece
+    pub fn from_char(c: char) -> Option<(PieceType, Color)> {
+        PieceType::from_fen_char(c)
+    }
+}
+
+impl From<Piece> for char {
+    fn from(piece: Piece) -> Self {
+        piece.fen_char()
+    }
+}
+
+impl TryFrom<char> for Piece {
+    type Error = ParsePieceError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        let (piece_type, color) =
+            PieceType::from_fen_char(value).ok_or(ParsePieceError::UnknownCharacterPiece)?;
+
         Ok(Piece { piece_type, color })
     }
 }
@@ -95,8 +214,92 @@ impl FromStr for Piece {
             .chars()
             .next()
             .ok_or(ParsePieceError::StringEmpty)
-            .and_then(|c| Piece::try_from(c));
+            .and_then(Piece::try_from);
 
         piece
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIECE_TYPES: [(PieceType, char); 6] = [
+        (PieceType::Pawn, 'p'),
+        (PieceType::Knight, 'n'),
+        (PieceType::Bishop, 'b'),
+        (PieceType::Rook, 'r'),
+        (PieceType::Queen, 'q'),
+        (PieceType::King, 'k'),
+    ];
+
+    #[test]
+    pub fn fen_chars_in_both_cases() {
+        for (piece_type, c) in PIECE_TYPES {
+            let upper = c.to_ascii_uppercase();
+
+            assert_eq!(piece_type.fen_char(Color::Black), c);
+            assert_eq!(piece_type.fen_char(Color::White), upper);
+            assert_eq!(
+                PieceType::from_fen_char(c),
+                Some((piece_type, Color::Black))
+            );
+            assert_eq!(
+                PieceType::from_fen_char(upper),
+                Some((piece_type, Color::White))
+            );
+        }
+
+        assert_eq!(PieceType::from_fen_char('x'), None);
+        assert_eq!(PieceType::from_fen_char('1'), None);
+    }
+
+    #[test]
+    pub fn pieces_round_trip_through_chars() {
+        for (piece_type, _) in PIECE_TYPES {
+            for color in [Color::White, Color::Black] {
+                let piece = Piece { piece_type, color };
+                let c = piece.fen_char();
+
+                assert_eq!(char::from(piece), c);
+                assert_eq!(Piece::try_from(c).unwrap(), piece);
+
+                assert_eq!(piece.to_fen_char(), c);
+                assert_eq!(piece_type.to_char(color), c);
+                assert_eq!(PieceType::from_char(c), Some((piece_type, color)));
+            }
+        }
+
+        assert_eq!(PieceType::from_char('x'), None);
+
+        assert!(Piece::try_from('x').is_err());
+        assert!(Piece::try_from(' ').is_err());
+    }
+
+    #[test]
+    pub fn color_and_piece_type_helpers() {
+        assert_eq!(Color::White.opposite(), Color::Black);
+        assert_eq!(Color::Black.opposite(), Color::White);
+        assert_eq!(!Color::White, Color::Black);
+        assert_eq!(!!Color::White, Color::White);
+        assert!(Color::White.is_white());
+        assert!(!Color::Black.is_white());
+
+        let sliders = PIECE_TYPES
+            .iter()
+            .filter(|(piece_type, _)| piece_type.is_slider())
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        assert_eq!(sliders, "brq");
+    }
+
+    #[test]
+    pub fn all_piece_types_once() {
+        let all = PieceType::all();
+
+        for (piece_type, _) in PIECE_TYPES {
+            assert_eq!(all.iter().filter(|p| **p == piece_type).count(), 1);
+        }
+        assert_eq!(all.len(), 6);
+    }
+}
@@ -0,0 +1,213 @@
+use std::path::Path;
+
+use crate::{error::PolyglotError, Game, Move, PieceType};
+
+/// Size in bytes of a single entry in a Polyglot book
+const ENTRY_SIZE: usize = 16;
+
+/// A single entry in a Polyglot book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PolyglotEntry {
+    key: u64,
+    mv: u16,
+    weight: u16,
+}
+
+/// A Polyglot opening book
+///
+/// Polyglot books (`.bin` files) are a list of entries sorted by the Polyglot key of the
+/// position, each entry holds a move and a weight for that move.
+///
+/// # Examples
+/// ```no_run
+/// use fritiofr_chess::{Game, PolyglotBook};
+///
+/// let book = PolyglotBook::open("book.bin").unwrap();
+/// let game = Game::start_pos();
+///
+/// for (mv, weight) in book.probe(&game) {
+///     println!("{:?} {}", mv, weight);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolyglotBook {
+    entries: Vec<PolyglotEntry>,
+}
+
+impl PolyglotBook {
+    /// Reads a Polyglot book from a file
+    ///
+    /// # Arguments
+    /// * `path` - The path to the `.bin` file
+    ///
+    /// # Returns
+    /// * `Result<PolyglotBook, PolyglotError>` - The book or an error if the file couldn't be read
+    ///   or isn't a valid book
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<PolyglotBook, PolyglotError> {
+        let bytes = std::fs::read(path)?;
+
+        PolyglotBook::from_bytes(&bytes)
+    }
+
+    /// Parses a Polyglot book from the raw bytes of a `.bin` file
+    ///
+    /// # Arguments
+    /// * `bytes` - The content of the book
+    ///
+    /// # Returns
+    /// * `Result<PolyglotBook, PolyglotError>` - The book or an error if the bytes isn't a valid
+    ///   book
+    pub fn from_bytes(bytes: &[u8]) -> Result<PolyglotBook, PolyglotError> {
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(PolyglotError::IncorrectLength);
+        }
+
+        let mut entries = bytes
+            .chunks_exact(ENTRY_SIZE)
+            .map(|chunk| PolyglotEntry {
+                key: u64::from_be_bytes(chunk[0..8].try_into().expect("Chunk is 16 bytes")),
+                mv: u16::from_be_bytes(chunk[8..10].try_into().expect("Chunk is 16 bytes")),
+                weight: u16::from_be_bytes(chunk[10..12].try_into().expect("Chunk is 16 bytes")),
+            })
+            .collect::<Vec<PolyglotEntry>>();
+
+        // Books should already be sorted, but probing relies on it so make sure
+        entries.sort_by_key(|e| e.key);
+
+        Ok(PolyglotBook { entries })
+    }
+
+    /// Returns the book moves for a position
+    ///
+    /// Entries that doesn't decode to a legal move in the position are skipped.
+    ///
+    /// # Arguments
+    /// * `game` - The position to look up
+    ///
+    /// # Returns
+    /// * `Vec<(Move, u16)>` - The book moves together with their weights, in the order they appear
+    ///   in the book
+    pub fn probe(&self, game: &Game) -> Vec<(Move, u16)> {
+        let key = game.polyglot_key();
+
+        let start = self.entries.partition_point(|e| e.key < key);
+
+        self.entries[start..]
+            .iter()
+            .take_while(|e| e.key == key)
+            .filter_map(|e| decode_move(game, e.mv).map(|mv| (mv, e.weight)))
+            .collect()
+    }
+}
+
+/// Internal helper that turns a Polyglot move into a legal move in the game
+///
+/// Polyglot moves are encoded as `to file | to row | from file | from row | promotion` with 3
+/// bits each, rows are counted from rank 1 like `y`. Castling is encoded as the king capturing
+/// its own rook.
+fn decode_move(game: &Game, mv: u16) -> Option<Move> {
+    let to_x = (mv & 0b111) as usize;
+    let to_y = ((mv >> 3) & 0b111) as usize;
+    let from_x = ((mv >> 6) & 0b111) as usize;
+    let from_y = ((mv >> 9) & 0b111) as usize;
+
+    let promotion = match (mv >> 12) & 0b111 {
+        0 => None,
+        1 => Some(PieceType::Knight),
+        2 => Some(PieceType::Bishop),
+        3 => Some(PieceType::Rook),
+        4 => Some(PieceType::Queen),
+        _ => return None,
+    };
+
+    game.gen_moves(from_x, from_y)?.into_iter().find(|m| {
+        if let Some(rook_from) = m.rook_from() {
+            return rook_from == (to_x, to_y);
+        }
+
+        m.to() == (to_x, to_y) && m.promotion() == promotion
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: u64, mv: u16, weight: u16) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&key.to_be_bytes());
+        bytes.extend_from_slice(&mv.to_be_bytes());
+        bytes.extend_from_slice(&weight.to_be_bytes());
+        bytes.extend_from_slice(&0u32.to_be_bytes());
+        bytes
+    }
+
+    /// Encodes a move the same way Polyglot does, rows count from rank 1 like `y`
+    fn polyglot_move(from: (u16, u16), to: (u16, u16)) -> u16 {
+        (from.1 << 9) | (from.0 << 6) | (to.1 << 3) | to.0
+    }
+
+    #[test]
+    pub fn probe_start_pos() {
+        let start_key = Game::start_pos().polyglot_key();
+        let after_e4 =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3").unwrap();
+
+        let mut bytes = vec![];
+        let mut entries = vec![
+            // e2e4 and d2d4 from the start position
+            (start_key, polyglot_move((4, 1), (4, 3)), 10),
+            (start_key, polyglot_move((3, 1), (3, 3)), 5),
+            // e7e5 after e2e4
+            (after_e4.polyglot_key(), polyglot_move((4, 6), (4, 4)), 1),
+        ];
+        entries.sort_by_key(|e| e.0);
+        for (key, mv, weight) in entries {
+            bytes.append(&mut entry(key, mv, weight));
+        }
+
+        let book = PolyglotBook::from_bytes(&bytes).unwrap();
+
+        let moves = book.probe(&Game::start_pos());
+        assert_eq!(moves.len(), 2);
+        assert!(moves.contains(&(
+            Move::DoublePawnPush {
+                from: (4, 1),
+                to: (4, 3)
+            },
+            10
+        )));
+        assert!(moves.contains(&(
+            Move::DoublePawnPush {
+                from: (3, 1),
+                to: (3, 3)
+            },
+            5
+        )));
+
+        assert_eq!(
+            book.probe(&after_e4),
+            vec![(after_e4.parse_san("e5").unwrap(), 1)]
+        );
+
+        let empty = book.probe(&Game::from_fen("8/8/8/8/8/8/8/K6k w - -").unwrap());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    pub fn probe_castle() {
+        let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq -").unwrap();
+
+        let bytes = entry(game.polyglot_key(), polyglot_move((4, 0), (7, 0)), 1);
+        let book = PolyglotBook::from_bytes(&bytes).unwrap();
+
+        let moves = book.probe(&game);
+        assert_eq!(moves.len(), 1);
+        assert!(moves[0].0.is_king_side_castle());
+    }
+
+    #[test]
+    pub fn incorrect_length() {
+        assert!(PolyglotBook::from_bytes(&[0; 15]).is_err());
+    }
+}
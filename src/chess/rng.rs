@@ -0,0 +1,141 @@
+/// A source of random numbers
+///
+/// Functions that need randomness take the random number generator as an argument instead of
+/// using a global one, so that results can be reproduced by using the same seed. Implement this
+/// trait to plug in any random number generator.
+///
+/// The crate doesn't depend on `rand`, so this trait is used instead of `rand::Rng`. A `rand`
+/// generator can't implement this trait directly outside of this crate, wrap it in a local type
+/// that forwards to `rand::RngCore::next_u64` instead. The example uses `rand` 0.9.
+///
+/// # Examples
+/// ```ignore
+/// use fritiofr_chess::{Game, Rng};
+/// use rand::{rngs::StdRng, RngCore, SeedableRng};
+///
+/// struct RandRng(StdRng);
+///
+/// impl Rng for RandRng {
+///     fn next_u64(&mut self) -> u64 {
+///         self.0.next_u64()
+///     }
+/// }
+///
+/// let mut rng = RandRng(StdRng::seed_from_u64(42));
+/// let mv = Game::start_pos().random_move(&mut rng);
+/// ```
+pub trait Rng {
+    /// Returns the next random number
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a uniformly random number in the range `0..n`
+    ///
+    /// Uses Lemire's multiply and reject method, so there is no modulo bias as long as
+    /// [`Rng::next_u64`] is uniform.
+    ///
+    /// # Arguments
+    /// * `n` - The upper bound, must be greater than 0
+    fn gen_below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "the upper bound must be greater than 0");
+
+        let mut m = self.next_u64() as u128 * n as u128;
+
+        // The low half is below 2^64 % n for the numbers that would make some results more
+        // likely than others, draw again for those
+        if (m as u64) < n {
+            let threshold = n.wrapping_neg() % n;
+
+            while (m as u64) < threshold {
+                m = self.next_u64() as u128 * n as u128;
+            }
+        }
+
+        (m >> 64) as u64
+    }
+}
+
+/// A small seedable xorshift random number generator
+///
+/// Good enough for picking moves, but not for anything that needs good randomness.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Rng, XorShiftRng};
+///
+/// let mut rng = XorShiftRng::new(42);
+/// let n = rng.gen_below(10);
+/// assert!(n < 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct XorShiftRng {
+    state: u64,
+}
+
+impl XorShiftRng {
+    /// Creates a new random number generator from a seed
+    pub fn new(seed: u64) -> XorShiftRng {
+        // Xorshift gets stuck on 0, so mix the seed into a non zero state
+        XorShiftRng {
+            state: seed ^ 0x9E37_79B9_7F4A_7C15 | 1,
+        }
+    }
+}
+
+impl Rng for XorShiftRng {
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn same_seed_gives_same_numbers() {
+        let mut a = XorShiftRng::new(1);
+        let mut b = XorShiftRng::new(1);
+        let mut c = XorShiftRng::new(2);
+
+        let a = (0..10).map(|_| a.next_u64()).collect::<Vec<u64>>();
+        let b = (0..10).map(|_| b.next_u64()).collect::<Vec<u64>>();
+        let c = (0..10).map(|_| c.next_u64()).collect::<Vec<u64>>();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    pub fn gen_below_is_in_range() {
+        let mut rng = XorShiftRng::new(7);
+
+        for n in [1, 2, 3, 10, 218, u64::MAX] {
+            for _ in 0..100 {
+                assert!(rng.gen_below(n) < n);
+            }
+        }
+        assert_eq!(rng.gen_below(1), 0);
+    }
+
+    #[test]
+    pub fn gen_below_rejects_biased_numbers() {
+        // Returns the numbers of a list in order
+        struct Sequence(Vec<u64>);
+
+        impl Rng for Sequence {
+            fn next_u64(&mut self) -> u64 {
+                self.0.remove(0)
+            }
+        }
+
+        // 2^64 isn't a multiple of n, 2 lands in the biased part and is drawn again, 1 gives 0
+        let n = (1 << 63) + 1;
+        let mut rng = Sequence(vec![2, 1]);
+
+        assert_eq!(rng.gen_below(n), 0);
+        assert!(rng.0.is_empty());
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{Color, Game, MoveList, PieceSquareTables, PieceType};
+
+/// The penalty for each pawn on a file with more than one pawn of the same color
+const DOUBLED_PAWN: i32 = -10;
+/// The penalty for each pawn without pawns of the same color on the files next to it
+const ISOLATED_PAWN: i32 = -15;
+/// The bonus for each pawn without enemy pawns in front of it
+const PASSED_PAWN: i32 = 20;
+/// The penalty for each point of pressure on the king, see [`Game::king_attack_zone_pressure`]
+const KING_PRESSURE: i32 = -4;
+/// The bonus for each pseudo legal move, see [`Game::mobility`]
+const MOBILITY: i32 = 2;
+
+/// A static evaluation used by the search, see [`Game::search_with_evaluator`]
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Color, Evaluator, Game, SearchOptions};
+///
+/// /// Likes to have the white king far up the board
+/// struct KingWalk;
+///
+/// impl Evaluator for KingWalk {
+///     fn eval(&self, game: &Game) -> i32 {
+///         let (_, y) = game.board().get_king_pos(Color::White).unwrap();
+///
+///         // The score is from the perspective of the side to move
+///         match game.turn() {
+///             Color::White => y as i32,
+///             Color::Black => -(y as i32),
+///         }
+///     }
+/// }
+///
+/// let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+/// let result = game
+///     .search_with_evaluator(1, SearchOptions::default(), &KingWalk)
+///     .unwrap();
+///
+/// assert_eq!(result.best.to().1, 1);
+/// ```
+pub trait Evaluator {
+    /// Returns the score of a position in centipawns, from the perspective of the side to move
+    ///
+    /// Checkmate and stalemate are found by the search, they don't have to be scored.
+    fn eval(&self, game: &Game) -> i32;
+}
+
+/// Only counts the material, with the values of [`PieceType::value`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaterialEvaluator;
+
+impl Evaluator for MaterialEvaluator {
+    fn eval(&self, game: &Game) -> i32 {
+        let score = game.material(Color::White) - game.material(Color::Black);
+
+        if game.turn() == Color::White {
+            score
+        } else {
+            -score
+        }
+    }
+}
+
+/// The evaluation of [`Game::evaluate`] with changeable piece-square tables
+///
+/// The default evaluator uses [`PieceSquareTables::DEFAULT`] and is the one used by
+/// [`Game::search`].
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{ClassicalEvaluator, Evaluator, Game, PieceSquareTables};
+///
+/// let game = Game::from_fen("4k3/8/8/8/8/8/8/Q3K3 b - -").unwrap();
+/// assert_eq!(ClassicalEvaluator::default().eval(&game), game.evaluate());
+///
+/// let evaluator = ClassicalEvaluator {
+///     tables: PieceSquareTables::zeros(),
+/// };
+/// assert_ne!(evaluator.eval(&game), game.evaluate());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClassicalEvaluator {
+    pub tables: PieceSquareTables,
+}
+
+impl Evaluator for ClassicalEvaluator {
+    fn eval(&self, game: &Game) -> i32 {
+        game.classical_evaluation(&self.tables)
+    }
+}
+
+impl Game {
+    /// Returns a static evaluation of the position in centipawns
+    ///
+    /// The score is from the perspective of the current turn, a positive score means the side to
+    /// move is better. The evaluation counts material, where the pieces stand, the pawn structure,
+    /// the pressure on the kings and the mobility. Checkmate and stalemate aren't detected, that is
+    /// left to the search.
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// assert_eq!(Game::start_pos().evaluate(), 0);
+    ///
+    /// // White is a queen up
+    /// let game = Game::from_fen("4k3/8/8/8/8/8/8/Q3K3 b - -").unwrap();
+    /// assert!(game.evaluate() < -800);
+    /// ```
+    pub fn evaluate(&self) -> i32 {
+        self.classical_evaluation(&PieceSquareTables::DEFAULT)
+    }
+
+    /// Internal helper that evaluates the position with some piece-square tables, see
+    /// [`Game::evaluate`]
+    fn classical_evaluation(&self, tables: &PieceSquareTables) -> i32 {
+        let phase = self.phase();
+        let score = self.material(Color::White) - self.material(Color::Black)
+            + tables.score(self.board(), Color::White, phase)
+            - tables.score(self.board(), Color::Black, phase)
+            + self.pawn_structure(Color::White)
+            - self.pawn_structure(Color::Black)
+            + self.king_attack_zone_pressure(Color::White) * KING_PRESSURE
+            - self.king_attack_zone_pressure(Color::Black) * KING_PRESSURE
+            + (self.mobility(Color::White) - self.mobility(Color::Black)) * MOBILITY;
+
+        if self.turn() == Color::White {
+            score
+        } else {
+            -score
+        }
+    }
+
+    /// Returns how far the game is from the endgame, based on the pieces left on the board
+    ///
+    /// Every knight and bishop counts 1, every rook 2 and every queen 4, for both colors. With all
+    /// pieces on the board the phase is 24, with only kings and pawns it is 0. This is used to
+    /// blend between middlegame and endgame evaluations. Promoted pieces can't raise the phase
+    /// above 24.
+    ///
+    /// # Returns
+    /// * `u8` - The phase from 0 (endgame) to 24 (opening)
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// assert_eq!(Game::start_pos().phase(), 24);
+    /// ```
+    pub fn phase(&self) -> u8 {
+        let phase = [Color::White, Color::Black]
+            .iter()
+            .map(|color| {
+                let count = |piece_type| self.board().piece_count(*color, piece_type) as u32;
+
+                count(PieceType::Knight)
+                    + count(PieceType::Bishop)
+                    + count(PieceType::Rook) * 2
+                    + count(PieceType::Queen) * 4
+            })
+            .sum::<u32>();
+
+        phase.min(24) as u8
+    }
+
+    /// Returns how many pseudo legal moves the pieces of a color have
+    ///
+    /// Unlike [`Game::gen_all_moves`] pins and checks are ignored, which makes this much faster
+    /// and works for the color that isn't to move. Castling isn't counted, and en passant is only
+    /// counted for the current turn.
+    ///
+    /// # Arguments
+    /// * `color` - The color to count the moves of
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Color, Game};
+    ///
+    /// // 16 pawn moves and 4 knight moves
+    /// assert_eq!(Game::start_pos().mobility(Color::Black), 20);
+    /// ```
+    pub fn mobility(&self, color: Color) -> i32 {
+        // The en passant tile only belongs to the current turn, changing the turn removes it
+        let game = self.with_turn(color);
+        let mut pieces = game.board().color_pieces(color);
+        let mut moves = MoveList::new();
+
+        while pieces != 0 {
+            let tile = pieces.trailing_zeros() as usize;
+            pieces &= pieces - 1;
+
+            game.gen_pseudo_legal_moves(tile % 8, tile / 8, true, &mut moves);
+        }
+
+        moves.len() as i32
+    }
+
+    /// Internal helper that sums the value of all pieces of a color
+    fn material(&self, color: Color) -> i32 {
+        PieceType::all()
+            .iter()
+            .map(|piece_type| {
+                self.board().piece_count(color, *piece_type) as i32 * piece_type.value()
+            })
+            .sum()
+    }
+
+    /// Internal helper that scores the doubled, isolated and passed pawns of a color
+    fn pawn_structure(&self, color: Color) -> i32 {
+        let board = self.board();
+
+        board.doubled_pawns(color) as i32 * DOUBLED_PAWN
+            + board.isolated_pawns(color) as i32 * ISOLATED_PAWN
+            + board.passed_pawns(color).len() as i32 * PASSED_PAWN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn phase_from_opening_to_endgame() {
+        assert_eq!(Game::start_pos().phase(), 24);
+
+        // Only kings and pawns
+        let game = Game::from_fen("4k3/pppp4/8/8/8/8/4PPPP/4K3 w - -").unwrap();
+        assert_eq!(game.phase(), 0);
+
+        // A rook and a knight against a queen
+        let game = Game::from_fen("3qk3/8/8/8/8/8/8/1N2K2R w - -").unwrap();
+        assert_eq!(game.phase(), 2 + 1 + 4);
+
+        // Extra queens don't go past the opening phase
+        let game = Game::from_fen("qqqqkqqq/8/8/8/8/8/8/QQQQKQQQ w - -").unwrap();
+        assert_eq!(game.phase(), 24);
+    }
+
+    #[test]
+    pub fn evaluation_is_the_same_for_the_mirrored_game() {
+        // The score is for the side to move, so the mirrored game has the same score
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - -",
+            "4k3/8/8/8/8/8/8/Q3K3 b - -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            assert_eq!(
+                game.evaluate(),
+                game.mirror_vertical().evaluate(),
+                "{}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    pub fn material_evaluator_only_counts_material() {
+        assert_eq!(MaterialEvaluator.eval(&Game::start_pos()), 0);
+
+        // The doubled pawns and the mobility don't matter
+        let game = Game::from_fen("4k3/8/8/8/8/2P5/2P5/R3K3 b - -").unwrap();
+        assert_eq!(MaterialEvaluator.eval(&game), -700);
+        assert_eq!(MaterialEvaluator.eval(&game.with_turn(Color::White)), 700);
+        assert_eq!(ClassicalEvaluator::default().eval(&game), game.evaluate());
+    }
+
+    #[test]
+    pub fn mobility_of_both_colors() {
+        let game = Game::start_pos();
+        assert_eq!(game.mobility(Color::White), 20);
+        assert_eq!(game.mobility(Color::White), game.mobility(Color::Black));
+
+        // The pinned knight on e7 still counts its moves
+        let game = Game::from_fen("4k3/4n3/8/8/8/8/8/4RK2 b - -").unwrap();
+        assert_eq!(game.mobility(Color::Black), 4 + 6);
+
+        // The black pawn on d4 can only capture en passant when it is black to move
+        let white_to_move = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 w - -").unwrap();
+        let black_to_move = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3").unwrap();
+        assert_eq!(
+            black_to_move.mobility(Color::Black),
+            white_to_move.mobility(Color::Black) + 1
+        );
+        assert_eq!(
+            black_to_move.mobility(Color::White),
+            white_to_move.mobility(Color::White)
+        );
+    }
+}
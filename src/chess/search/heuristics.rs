@@ -0,0 +1,102 @@
+use alloc::{vec, vec::Vec};
+
+use crate::Move;
+
+/// The amount of killer moves remembered for each ply
+const KILLERS_PER_PLY: usize = 2;
+
+/// Internal table of quiet moves that caused a beta cutoff, two for each ply
+///
+/// Positions at the same ply are often similar, so a move that refuted one of them is likely to
+/// refute the others as well.
+#[derive(Default)]
+pub(super) struct KillerMoves {
+    killers: Vec<[Option<Move>; KILLERS_PER_PLY]>,
+}
+
+impl KillerMoves {
+    /// Returns which killer slot a move is in at a ply, 0 is the most recent
+    pub fn slot(&self, ply: u32, mv: Move) -> Option<usize> {
+        self.killers
+            .get(ply as usize)?
+            .iter()
+            .position(|killer| *killer == Some(mv))
+    }
+
+    /// Remembers a move that caused a cutoff, pushing out the oldest killer of the ply
+    pub fn store(&mut self, ply: u32, mv: Move) {
+        let ply = ply as usize;
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; KILLERS_PER_PLY]);
+        }
+
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(mv) {
+            killers.rotate_right(1);
+            killers[0] = Some(mv);
+        }
+    }
+}
+
+/// Internal table of how often quiet moves caused a beta cutoff, indexed by the from and to tiles
+///
+/// Unlike killer moves the history is shared between all plies, deeper cutoffs count more.
+pub(super) struct HistoryTable {
+    scores: Vec<[i32; 64]>,
+}
+
+impl HistoryTable {
+    /// The highest score a move can get, so history never outranks captures and killers
+    pub const MAX: i32 = 8_000;
+
+    pub fn new() -> HistoryTable {
+        HistoryTable {
+            scores: vec![[0; 64]; 64],
+        }
+    }
+
+    /// Returns the history score of a move
+    pub fn get(&self, mv: Move) -> i32 {
+        let (from, to) = tiles(mv);
+        self.scores[from][to]
+    }
+
+    /// Rewards a move that caused a cutoff at a depth
+    pub fn reward(&mut self, mv: Move, depth: u32) {
+        let (from, to) = tiles(mv);
+        let score = &mut self.scores[from][to];
+        *score = (*score + (depth * depth) as i32).min(HistoryTable::MAX);
+    }
+}
+
+/// Internal helper that returns the from and to tiles of a move as `y * 8 + x`
+fn tiles(mv: Move) -> (usize, usize) {
+    let (from_x, from_y) = mv.from();
+    let (to_x, to_y) = mv.to();
+    (from_y * 8 + from_x, to_y * 8 + to_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn killers_keep_the_two_latest_moves() {
+        let mv = |to_x| Move::Quiet {
+            from: (4, 1),
+            to: (to_x, 2),
+        };
+        let mut killers = KillerMoves::default();
+
+        killers.store(3, mv(0));
+        killers.store(3, mv(1));
+        killers.store(3, mv(1));
+        assert_eq!(killers.slot(3, mv(1)), Some(0));
+        assert_eq!(killers.slot(3, mv(0)), Some(1));
+        assert_eq!(killers.slot(2, mv(0)), None);
+
+        killers.store(3, mv(2));
+        assert_eq!(killers.slot(3, mv(0)), None);
+        assert_eq!(killers.slot(3, mv(2)), Some(0));
+    }
+}
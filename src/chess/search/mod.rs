@@ -0,0 +1,1152 @@
+//! Searching for the best move with alpha beta search
+//!
+//! The search is a negamax with alpha beta pruning that deepens iteratively, followed by a
+//! quiescence search of captures so the evaluation isn't done in the middle of an exchange.
+//! Positions that were already searched are kept in a transposition table, which also gives the
+//! move ordering of the next iteration. With the `std` feature the search can stop when a time
+//! limit runs out.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{Game, Move, MoveList, PieceType};
+
+mod eval;
+pub use eval::{ClassicalEvaluator, Evaluator, MaterialEvaluator};
+mod heuristics;
+mod pst;
+pub use pst::{PieceSquareTables, Table};
+mod tt;
+
+use heuristics::{HistoryTable, KillerMoves};
+use tt::{Bound, Entry, TranspositionTable};
+
+/// The score of being checkmated at the root, mates further away score closer to zero
+///
+/// A score of `MATE_SCORE - n` means the side to move mates in `n` half moves.
+pub const MATE_SCORE: i32 = 100_000;
+
+/// The result of a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// The best move found, the same as the first move of `pv`
+    pub best: Move,
+    /// The score of the position in centipawns, from the perspective of the side to move
+    pub score: i32,
+    /// The principal variation, the line the search expects to be played
+    ///
+    /// The line is always legal from the searched position. It's as long as the searched depth
+    /// and the check extensions along it, it's shorter if the game ends or a part of the line has
+    /// been pushed out of the transposition table.
+    pub pv: Vec<Move>,
+    /// The amount of positions that were searched, including the quiescence search
+    pub nodes: u64,
+    /// The depth that was searched, for a timed search the depth of the last finished iteration
+    pub depth: u32,
+}
+
+/// How many moves of a position are searched at the full depth before late move reductions
+const FULL_DEPTH_MOVES: usize = 3;
+
+/// The least depth where late moves are reduced
+const LATE_MOVE_REDUCTION_DEPTH: u32 = 3;
+
+/// How many nodes are searched between each look at the clock
+#[cfg(feature = "std")]
+const NODES_BETWEEN_TIME_CHECKS: u64 = 1024;
+
+/// Options that turn parts of the search on and off
+///
+/// The defaults are what gives the strongest search, turning parts off is mostly useful for
+/// comparing node counts.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Game, SearchOptions};
+///
+/// let game = Game::start_pos();
+/// let options = SearchOptions {
+///     transposition_table: false,
+///     ..SearchOptions::default()
+/// };
+///
+/// let result = game.search_with_options(3, options).unwrap();
+/// assert!(result.nodes > 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Remember searched positions so they aren't searched again when reached through another
+    /// move order
+    pub transposition_table: bool,
+    /// Try quiet moves that caused a cutoff in a sibling position early
+    pub killer_moves: bool,
+    /// Try quiet moves that often caused cutoffs anywhere in the search early
+    pub history: bool,
+    /// Let the opponent move twice and skip the position if it's still good enough, searching
+    /// that with the depth reduced by this much. None turns null move pruning off
+    ///
+    /// It's never done when in check or with only pawns left, where passing can be better than
+    /// any move (zugzwang).
+    pub null_move_reduction: Option<u32>,
+    /// Search every depth after the first with the window this far around the score of the
+    /// depth before, in centipawns. None always searches with the full window
+    ///
+    /// A narrow window cuts off more moves, if the score falls outside it the depth is searched
+    /// again with the window widened on that side.
+    pub aspiration_window: Option<i32>,
+    /// Search one half move deeper when the side to move is in check, so forcing lines are
+    /// searched to the end
+    ///
+    /// A line is never extended by more than the searched depth, so long series of checks can't
+    /// make the search explode.
+    pub check_extensions: bool,
+    /// Search quiet moves late in the move ordering with the depth reduced by this much, and
+    /// only search them again at the full depth if they turn out better than the moves before.
+    /// None turns late move reductions off
+    ///
+    /// Captures, promotions, moves that give check, moves out of check and the moves of the
+    /// searched position itself are never reduced.
+    pub late_move_reduction: Option<u32>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            transposition_table: true,
+            killer_moves: true,
+            history: true,
+            null_move_reduction: Some(2),
+            aspiration_window: Some(50),
+            check_extensions: true,
+            late_move_reduction: Some(1),
+        }
+    }
+}
+
+impl Game {
+    /// Searches for the best move
+    ///
+    /// # Arguments
+    /// * `depth` - How many half moves to search before only captures are searched, a depth of 0
+    ///   is searched as 1
+    ///
+    /// # Returns
+    /// * `Option<SearchResult>` - The best move, its score and the expected line, or None if there
+    ///   are no legal moves
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::Game;
+    ///
+    /// // The black queen is hanging
+    /// let game = Game::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - -").unwrap();
+    /// let result = game.search(2).unwrap();
+    ///
+    /// assert_eq!(result.best, game.parse_san("Rxd5").unwrap());
+    /// assert_eq!(result.pv[0], result.best);
+    /// ```
+    pub fn search(&self, depth: u32) -> Option<SearchResult> {
+        self.search_with_options(depth, SearchOptions::default())
+    }
+
+    /// Searches for the best move with some parts of the search turned on or off
+    ///
+    /// # Arguments
+    /// * `depth` - How many half moves to search, see [`Game::search`]
+    /// * `options` - The parts of the search to use
+    ///
+    /// # Returns
+    /// * `Option<SearchResult>` - The best move, its score and the expected line, or None if there
+    ///   are no legal moves
+    pub fn search_with_options(&self, depth: u32, options: SearchOptions) -> Option<SearchResult> {
+        self.search_with_evaluator(depth, options, &ClassicalEvaluator::default())
+    }
+
+    /// Searches for the best move with another evaluation than [`Game::evaluate`]
+    ///
+    /// # Arguments
+    /// * `depth` - How many half moves to search, see [`Game::search`]
+    /// * `options` - The parts of the search to use
+    /// * `evaluator` - Scores the positions at the end of the search
+    ///
+    /// # Returns
+    /// * `Option<SearchResult>` - The best move, its score and the expected line, or None if there
+    ///   are no legal moves
+    ///
+    /// # Examples
+    /// ```
+    /// use fritiofr_chess::{Game, MaterialEvaluator, SearchOptions};
+    ///
+    /// let game = Game::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - -").unwrap();
+    /// let result = game
+    ///     .search_with_evaluator(2, SearchOptions::default(), &MaterialEvaluator)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.best, game.parse_san("Rxd5").unwrap());
+    /// ```
+    pub fn search_with_evaluator(
+        &self,
+        depth: u32,
+        options: SearchOptions,
+        evaluator: &impl Evaluator,
+    ) -> Option<SearchResult> {
+        Search::new(options, evaluator).iterate(self, depth.max(1))
+    }
+
+    /// Searches deeper and deeper until a time limit or a maximum depth is reached
+    ///
+    /// Like [`Game::search`] each depth is searched in turn, but an iteration that runs out of
+    /// time is thrown away and the result of the last finished one is returned. The first
+    /// iteration is always finished, so there is a move even with no time at all.
+    ///
+    /// # Arguments
+    /// * `max_depth` - The deepest iteration to search
+    /// * `time` - How long the search may take
+    ///
+    /// # Returns
+    /// * `Option<SearchResult>` - The result of the deepest finished iteration, or None if there
+    ///   are no legal moves
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use fritiofr_chess::Game;
+    ///
+    /// let game = Game::start_pos();
+    /// let result = game.search_timed(64, Duration::from_millis(50)).unwrap();
+    ///
+    /// assert!(result.depth >= 1);
+    /// assert!(game.gen_all_moves().unwrap().contains(&result.best));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn search_timed(&self, max_depth: u32, time: std::time::Duration) -> Option<SearchResult> {
+        self.search_timed_with_evaluator(
+            max_depth,
+            time,
+            SearchOptions::default(),
+            &ClassicalEvaluator::default(),
+        )
+    }
+
+    /// Searches deeper and deeper until a time limit or a maximum depth is reached, with another
+    /// evaluation than [`Game::evaluate`]
+    ///
+    /// # Arguments
+    /// * `max_depth` - The deepest iteration to search
+    /// * `time` - How long the search may take, see [`Game::search_timed`]
+    /// * `options` - The parts of the search to use
+    /// * `evaluator` - Scores the positions at the end of the search
+    ///
+    /// # Returns
+    /// * `Option<SearchResult>` - The result of the deepest finished iteration, or None if there
+    ///   are no legal moves
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use fritiofr_chess::{Game, MaterialEvaluator, SearchOptions};
+    ///
+    /// let game = Game::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - -").unwrap();
+    /// let result = game
+    ///     .search_timed_with_evaluator(
+    ///         2,
+    ///         Duration::from_secs(60),
+    ///         SearchOptions::default(),
+    ///         &MaterialEvaluator,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.best, game.parse_san("Rxd5").unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn search_timed_with_evaluator(
+        &self,
+        max_depth: u32,
+        time: std::time::Duration,
+        options: SearchOptions,
+        evaluator: &impl Evaluator,
+    ) -> Option<SearchResult> {
+        let mut search = Search::new(options, evaluator);
+        search.deadline = Some(std::time::Instant::now() + time);
+
+        search.iterate(self, max_depth.max(1))
+    }
+
+    /// Returns the best move found by a search
+    ///
+    /// The same as [`Game::search`], but only the move is returned.
+    ///
+    /// # Arguments
+    /// * `depth` - How many half moves to search
+    pub fn best_move(&self, depth: u32) -> Option<Move> {
+        self.search(depth).map(|result| result.best)
+    }
+}
+
+/// Internal state of a running search
+struct Search<'a, E: Evaluator> {
+    evaluator: &'a E,
+    nodes: u64,
+    tt: Option<TranspositionTable>,
+    killers: Option<KillerMoves>,
+    history: Option<HistoryTable>,
+    null_move_reduction: Option<u32>,
+    aspiration_window: Option<i32>,
+    check_extensions: bool,
+    late_move_reduction: Option<u32>,
+    /// The depth of the current iteration, to bound the check extensions
+    root_depth: u32,
+    /// Set while searching the position after a null move, so two aren't made in a row
+    after_null_move: bool,
+    /// The keys of the positions from the root to the current position, to find repetitions
+    path: Vec<u64>,
+    /// When the search has to stop, checked every [`NODES_BETWEEN_TIME_CHECKS`] nodes
+    #[cfg(feature = "std")]
+    deadline: Option<std::time::Instant>,
+    /// Set when the deadline has passed, the scores of an aborted search are meaningless
+    aborted: bool,
+}
+
+impl<'a, E: Evaluator> Search<'a, E> {
+    fn new(options: SearchOptions, evaluator: &'a E) -> Search<'a, E> {
+        Search {
+            evaluator,
+            nodes: 0,
+            tt: options.transposition_table.then(TranspositionTable::new),
+            killers: options.killer_moves.then(KillerMoves::default),
+            history: options.history.then(HistoryTable::new),
+            null_move_reduction: options.null_move_reduction,
+            aspiration_window: options.aspiration_window,
+            check_extensions: options.check_extensions,
+            late_move_reduction: options.late_move_reduction,
+            root_depth: 0,
+            after_null_move: false,
+            path: vec![],
+            #[cfg(feature = "std")]
+            deadline: None,
+            aborted: false,
+        }
+    }
+
+    /// Internal helper that searches the root position at every depth up to `max_depth`
+    ///
+    /// Every iteration fills the transposition table and the move ordering tables, so the next
+    /// one searches the best moves first. If there is a deadline the search stops when it has
+    /// passed and the result of the last finished iteration is returned.
+    fn iterate(&mut self, game: &Game, max_depth: u32) -> Option<SearchResult> {
+        // The first iteration is always finished so there is a move to return
+        #[cfg(feature = "std")]
+        let deadline = self.deadline.take();
+        let mut result = self.search_root(game, 1, None)?;
+        #[cfg(feature = "std")]
+        {
+            self.deadline = deadline;
+        }
+
+        for depth in 2..=max_depth {
+            #[cfg(feature = "std")]
+            if self
+                .deadline
+                .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+            {
+                break;
+            }
+
+            match self.search_root(game, depth, Some(result.score)) {
+                Some(iteration) if !self.aborted => result = iteration,
+                _ => break,
+            }
+        }
+
+        result.nodes = self.nodes;
+        Some(result)
+    }
+
+    /// Internal helper that searches the root position to a depth
+    ///
+    /// # Arguments
+    /// * `guess` - The score of the depth before, the search starts with an aspiration window
+    ///   around it
+    fn search_root(&mut self, game: &Game, depth: u32, guess: Option<i32>) -> Option<SearchResult> {
+        const MIN: i32 = -MATE_SCORE - 1;
+        const MAX: i32 = MATE_SCORE + 1;
+
+        self.root_depth = depth;
+
+        // Mate scores jump between depths, so they are always searched with the full window
+        let (mut alpha, mut beta, mut window) = match (self.aspiration_window, guess) {
+            (Some(window), Some(guess)) if guess.abs() < MATE_SCORE / 2 => {
+                // An empty window could never grow
+                let window = window.max(1);
+                (guess - window, guess + window, window)
+            }
+            _ => (MIN, MAX, 0),
+        };
+
+        loop {
+            let mut pv = vec![];
+            let score = self.negamax(game, depth, 0, alpha, beta, &mut pv);
+
+            if self.aborted {
+                return None;
+            }
+
+            // Outside the window the score is only a bound, so the side it failed on is widened
+            if score <= alpha && alpha > MIN {
+                window = window.saturating_mul(2);
+                alpha = guess.map_or(MIN, |guess| guess.saturating_sub(window).max(MIN));
+            } else if score >= beta && beta < MAX {
+                window = window.saturating_mul(2);
+                beta = guess.map_or(MAX, |guess| guess.saturating_add(window).min(MAX));
+            } else {
+                return Some(SearchResult {
+                    best: *pv.first()?,
+                    score,
+                    pv,
+                    nodes: self.nodes,
+                    depth,
+                });
+            }
+        }
+    }
+
+    /// Internal helper that follows the best moves stored in the transposition table
+    ///
+    /// This rebuilds the PV below a position that was cut off by the table. Every move is checked
+    /// to be legal, since a different position can share the same table slot.
+    fn tt_line(&self, game: &Game, max_len: u32) -> Vec<Move> {
+        let mut line = vec![];
+        let Some(tt) = &self.tt else {
+            return line;
+        };
+
+        let mut game = *game;
+        while line.len() < max_len as usize {
+            let Some(mv) = tt.probe(game.polyglot_key(), 0).and_then(|e| e.best) else {
+                break;
+            };
+
+            if !game.legal_moves_iter().any(|m| m == mv) {
+                break;
+            }
+
+            game.apply_move(mv)
+                .expect("The move was checked to be legal");
+            line.push(mv);
+        }
+
+        line
+    }
+
+    /// Internal helper that counts a node and returns if the search should stop
+    fn visit(&mut self) -> bool {
+        self.nodes += 1;
+
+        #[cfg(feature = "std")]
+        if self.nodes.is_multiple_of(NODES_BETWEEN_TIME_CHECKS) {
+            if let Some(deadline) = self.deadline {
+                self.aborted |= std::time::Instant::now() >= deadline;
+            }
+        }
+
+        self.aborted
+    }
+
+    /// Internal helper that scores a move for move ordering, higher is searched first
+    ///
+    /// The move from the transposition table comes first, then captures and promotions, then the
+    /// killer moves and last the rest of the quiet moves by their history.
+    fn move_order_score(&self, game: &Game, mv: Move, ply: u32, tt_move: Option<Move>) -> i32 {
+        if Some(mv) == tt_move {
+            return i32::MAX;
+        }
+
+        if mv.is_capture() || mv.is_promotion() {
+            return game.move_order_score(&mv);
+        }
+
+        if let Some(slot) = self.killers.as_ref().and_then(|k| k.slot(ply, mv)) {
+            return HistoryTable::MAX + 2 - slot as i32;
+        }
+
+        self.history.as_ref().map_or(0, |h| h.get(mv))
+    }
+
+    /// Internal helper that searches a position and returns its score
+    ///
+    /// # Arguments
+    /// * `game` - The position to search
+    /// * `depth` - The remaining depth
+    /// * `ply` - How many half moves from the root the position is
+    /// * `alpha` - The score the side to move is already guaranteed
+    /// * `beta` - The score the opponent is already guaranteed
+    /// * `pv` - Filled with the best line from this position
+    fn negamax(
+        &mut self,
+        game: &Game,
+        depth: u32,
+        ply: u32,
+        alpha: i32,
+        beta: i32,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        pv.clear();
+        if self.visit() {
+            return 0;
+        }
+
+        // Repeating a position along the line is scored as a draw, since the side that can't make
+        // progress can keep repeating it. Positions from before the last capture or pawn move
+        // can't come back, so only those after it are compared
+        let key = game.polyglot_key();
+        if ply > 0
+            && self
+                .path
+                .iter()
+                .rev()
+                .take(game.halfmove_clock() as usize)
+                .any(|k| *k == key)
+        {
+            return 0;
+        }
+
+        self.path.push(key);
+        let score = self.search_node(game, depth, ply, alpha, beta, pv);
+        self.path.pop();
+
+        score
+    }
+
+    /// Internal helper that searches the moves of a position, the position must be the last one
+    /// in `path`
+    fn search_node(
+        &mut self,
+        game: &Game,
+        mut depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        let key = *self
+            .path
+            .last()
+            .expect("The position is pushed before it's searched");
+
+        let mut moves = MoveList::new();
+        game.gen_all_moves_into(&mut moves);
+
+        let in_check = game.is_check();
+
+        if moves.is_empty() {
+            return if in_check {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            };
+        }
+
+        // Without extensions `ply + depth` is the root depth, so this allows as many extensions
+        // along a line as the root depth
+        if self.check_extensions && in_check && ply + depth < 2 * self.root_depth {
+            depth += 1;
+        }
+
+        if depth == 0 {
+            return self.quiescence(game, alpha, beta);
+        }
+
+        let entry = self.tt.as_ref().and_then(|tt| tt.probe(key, ply));
+
+        // The root is always searched so there is a best move and a PV to return
+        if let Some(entry) = entry.filter(|e| ply > 0 && e.depth >= depth) {
+            match entry.bound {
+                Bound::Exact => {
+                    pv.extend(self.tt_line(game, depth));
+                    return entry.score;
+                }
+                Bound::Lower if entry.score >= beta => return entry.score,
+                Bound::Upper if entry.score <= alpha => return entry.score,
+                _ => {}
+            }
+        }
+
+        let after_null_move = core::mem::replace(&mut self.after_null_move, false);
+        if let Some(reduction) = self.null_move_reduction {
+            if ply > 0
+                && !after_null_move
+                && depth > reduction
+                && beta.abs() < MATE_SCORE / 2
+                && !in_check
+                && has_pieces(game)
+            {
+                let mut child = *game;
+                let _ = child.make_null_move();
+
+                self.after_null_move = true;
+                let score = -self.negamax(
+                    &child,
+                    depth - 1 - reduction,
+                    ply + 1,
+                    -beta,
+                    -beta + 1,
+                    &mut vec![],
+                );
+                // The child can return before it reads the flag, e.g on a repetition or a table hit
+                self.after_null_move = false;
+
+                if self.aborted {
+                    return 0;
+                }
+
+                if score >= beta {
+                    return beta;
+                }
+            }
+        }
+
+        // The best move from an earlier search of the position is the most likely to be best
+        let tt_move = entry.and_then(|e| e.best);
+        moves.sort_by_key(|m| core::cmp::Reverse(self.move_order_score(game, *m, ply, tt_move)));
+
+        let original_alpha = alpha;
+        let mut best = -MATE_SCORE - 1;
+        let mut best_move = None;
+        let mut line = vec![];
+
+        for (i, mv) in moves.into_iter().enumerate() {
+            let mut child = *game;
+            child
+                .apply_move(mv)
+                .expect("gen_all_moves only returns valid moves");
+
+            // Thanks to the move ordering the late quiet moves are rarely the best, so they are
+            // first searched with a null window at a reduced depth to prove that they aren't
+            let reduction = self.late_move_reduction.filter(|_| {
+                ply > 0
+                    && i >= FULL_DEPTH_MOVES
+                    && depth >= LATE_MOVE_REDUCTION_DEPTH
+                    && !in_check
+                    && !mv.is_capture()
+                    && !mv.is_promotion()
+                    && !child.is_check()
+            });
+
+            let reduced_score = reduction.map(|reduction| {
+                -self.negamax(
+                    &child,
+                    (depth - 1).saturating_sub(reduction),
+                    ply + 1,
+                    -alpha - 1,
+                    -alpha,
+                    &mut line,
+                )
+            });
+
+            let score = match reduced_score {
+                Some(score) if score <= alpha => score,
+                _ => -self.negamax(&child, depth - 1, ply + 1, -beta, -alpha, &mut line),
+            };
+
+            if score > best {
+                best = score;
+                best_move = Some(mv);
+            }
+
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(mv);
+                pv.extend_from_slice(&line);
+            }
+
+            if alpha >= beta {
+                if !mv.is_capture() && !mv.is_promotion() {
+                    if let Some(killers) = &mut self.killers {
+                        killers.store(ply, mv);
+                    }
+                    if let Some(history) = &mut self.history {
+                        history.reward(mv, depth);
+                    }
+                }
+
+                break;
+            }
+
+            if self.aborted {
+                return 0;
+            }
+        }
+
+        if let Some(tt) = &mut self.tt {
+            let bound = if best <= original_alpha {
+                Bound::Upper
+            } else if best >= beta {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+
+            tt.store(
+                Entry {
+                    key,
+                    depth,
+                    score: best,
+                    bound,
+                    best: best_move,
+                },
+                ply,
+            );
+        }
+
+        best
+    }
+
+    /// Internal helper that only searches captures and promotions until the position is quiet
+    fn quiescence(&mut self, game: &Game, mut alpha: i32, beta: i32) -> i32 {
+        if self.visit() {
+            return 0;
+        }
+
+        let stand_pat = self.evaluator.eval(game);
+        if stand_pat >= beta {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut moves = MoveList::new();
+        game.gen_all_moves_into(&mut moves);
+        moves.sort_by_key(|m| -game.move_order_score(m));
+
+        for mv in moves {
+            if mv.capture().is_none() && !mv.is_promotion() {
+                continue;
+            }
+
+            // Captures that lose material are very unlikely to be the best move
+            if game.see(mv) < 0 {
+                continue;
+            }
+
+            let mut child = *game;
+            child
+                .apply_move(mv)
+                .expect("gen_all_moves only returns valid moves");
+
+            let score = -self.quiescence(&child, -beta, -alpha);
+
+            if score >= beta {
+                return score;
+            }
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+}
+
+/// Internal helper that checks if the side to move has any pieces other than pawns and the king
+fn has_pieces(game: &Game) -> bool {
+    [
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Queen,
+    ]
+    .into_iter()
+    .any(|piece_type| game.board().pieces(piece_type, game.turn()) != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn pv_starts_with_the_best_move_and_is_legal() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq -",
+            "4k3/8/8/3q4/8/8/8/3RK3 w - -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            let result = game.search(3).unwrap();
+
+            assert_eq!(result.pv[0], result.best, "{}", fen);
+            // Check extensions can at most double the length
+            assert!(result.pv.len() <= 6, "{}", fen);
+            assert!(result.nodes > 0);
+
+            let mut replay = game;
+            for mv in &result.pv {
+                assert!(replay.gen_all_moves().unwrap().contains(mv), "{}", fen);
+                replay.apply_move(*mv).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    pub fn search_finds_mate() {
+        let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - -").unwrap();
+        let result = game.search(2).unwrap();
+
+        assert_eq!(result.best, game.parse_san("Ra8#").unwrap());
+        assert_eq!(result.score, MATE_SCORE - 1);
+        assert_eq!(result.pv, vec![result.best]);
+    }
+
+    #[test]
+    pub fn transposition_table_reduces_nodes() {
+        let game =
+            Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -")
+                .unwrap();
+
+        let with_tt = game.search(4).unwrap();
+        let without_tt = game
+            .search_with_options(
+                4,
+                SearchOptions {
+                    transposition_table: false,
+                    ..SearchOptions::default()
+                },
+            )
+            .unwrap();
+
+        assert!(
+            with_tt.nodes < without_tt.nodes,
+            "{} >= {}",
+            with_tt.nodes,
+            without_tt.nodes
+        );
+        assert_eq!(with_tt.pv[0], with_tt.best);
+    }
+
+    #[test]
+    pub fn killers_and_history_reduce_nodes() {
+        let game =
+            Game::from_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq -")
+                .unwrap();
+
+        let with_heuristics = game.search(4).unwrap();
+        let without_heuristics = game
+            .search_with_options(
+                4,
+                SearchOptions {
+                    killer_moves: false,
+                    history: false,
+                    ..SearchOptions::default()
+                },
+            )
+            .unwrap();
+
+        assert!(
+            with_heuristics.nodes < without_heuristics.nodes,
+            "{} >= {}",
+            with_heuristics.nodes,
+            without_heuristics.nodes
+        );
+        assert_eq!(with_heuristics.score, without_heuristics.score);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn timed_search_returns_a_legal_move() {
+        use std::time::{Duration, Instant};
+
+        let game =
+            Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -")
+                .unwrap();
+
+        let start = Instant::now();
+        let result = game.search_timed(u32::MAX, Duration::ZERO).unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(result.depth, 1);
+        assert!(game.gen_all_moves().unwrap().contains(&result.best));
+
+        // Without a real time limit the maximum depth stops the search
+        let result = game.search_timed(3, Duration::from_secs(3600)).unwrap();
+        assert_eq!(result.depth, 3);
+        assert_eq!(result.best, game.search(3).unwrap().best);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn timed_search_uses_the_evaluator() {
+        use std::time::Duration;
+
+        struct Constant;
+
+        impl Evaluator for Constant {
+            fn eval(&self, _: &Game) -> i32 {
+                42
+            }
+        }
+
+        let game = Game::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - -").unwrap();
+        let result = game
+            .search_timed_with_evaluator(
+                2,
+                Duration::from_secs(3600),
+                SearchOptions::default(),
+                &Constant,
+            )
+            .unwrap();
+
+        assert_eq!(result.depth, 2);
+        assert_eq!(result.score, 42);
+    }
+
+    #[test]
+    pub fn null_move_pruning_reduces_nodes() {
+        let game =
+            Game::from_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq -")
+                .unwrap();
+
+        let with_null_move = game.search(5).unwrap();
+        let without_null_move = game
+            .search_with_options(
+                5,
+                SearchOptions {
+                    null_move_reduction: None,
+                    ..SearchOptions::default()
+                },
+            )
+            .unwrap();
+
+        assert!(
+            with_null_move.nodes < without_null_move.nodes,
+            "{} >= {}",
+            with_null_move.nodes,
+            without_null_move.nodes
+        );
+    }
+
+    #[test]
+    pub fn null_move_pruning_isnt_used_in_zugzwang() {
+        // With only kings and pawns both sides are often in zugzwang
+        let game = Game::from_fen("8/8/1p6/kP6/8/1K6/8/8 w - -").unwrap();
+        assert!(!has_pieces(&game));
+
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - -").unwrap();
+        assert!(has_pieces(&game));
+        assert!(!has_pieces(&game.with_turn(crate::Color::Black)));
+    }
+
+    #[test]
+    pub fn pv_of_a_forced_mate_ends_in_checkmate() {
+        for (fen, length) in [
+            ("6k1/5ppp/8/8/8/8/8/R5K1 w - -", 1),
+            ("kbK5/pp6/1P6/8/8/8/8/R7 w - -", 3),
+            ("r5rk/5p1p/5R2/4B3/8/8/7P/7K w - -", 5),
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            let result = game.search(5).unwrap();
+
+            assert_eq!(result.score, MATE_SCORE - length as i32, "{}", fen);
+            assert_eq!(result.pv.len(), length, "{}", fen);
+
+            let mut replay = game;
+            for mv in &result.pv {
+                assert!(replay.gen_all_moves().unwrap().contains(mv), "{}", fen);
+                replay.apply_move(*mv).unwrap();
+            }
+            assert!(replay.is_checkmate(), "{}", fen);
+        }
+    }
+
+    #[test]
+    pub fn repetition_along_the_line_is_a_draw() {
+        // White is a rook and a pawn down and black threatens mate, but white can check forever
+        // with Qe8+ Kh7 Qh5+ Kg8 Qe8+
+        let game = Game::from_fen("6k1/6p1/6Q1/8/8/8/qr6/7K w - -").unwrap();
+        let result = game.search(5).unwrap();
+
+        assert_eq!(result.score, 0);
+        assert_eq!(result.best, game.parse_san("Qe8+").unwrap());
+    }
+
+    #[test]
+    pub fn aspiration_windows_find_the_same_move() {
+        // The reductions depend on the order the moves are searched in, which can change the score
+        let options = SearchOptions {
+            null_move_reduction: None,
+            late_move_reduction: None,
+            ..SearchOptions::default()
+        };
+        let full_window = SearchOptions {
+            aspiration_window: None,
+            ..options
+        };
+
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq -",
+            "4k3/8/8/3q4/8/8/8/3RK3 w - -",
+            "r5rk/5p1p/5R2/4B3/8/8/7P/7K w - -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+
+            let aspiration = game.search_with_options(4, options).unwrap();
+            let full = game.search_with_options(4, full_window).unwrap();
+
+            assert_eq!(aspiration.best, full.best, "{}", fen);
+            assert_eq!(aspiration.score, full.score, "{}", fen);
+        }
+
+        // A window without any width fails on every depth, but still finds the move
+        let game = Game::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - -").unwrap();
+        let narrow = SearchOptions {
+            aspiration_window: Some(0),
+            ..SearchOptions::default()
+        };
+        assert_eq!(
+            game.search_with_options(4, narrow).unwrap().best,
+            game.parse_san("Rxd5").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn check_extensions_find_deeper_mates() {
+        // The mate takes five half moves, one more than the depth, but it's full of checks
+        let game = Game::from_fen("r5rk/5p1p/5R2/4B3/8/8/7P/7K w - -").unwrap();
+
+        let without = game
+            .search_with_options(
+                4,
+                SearchOptions {
+                    check_extensions: false,
+                    ..SearchOptions::default()
+                },
+            )
+            .unwrap();
+        assert!(without.score < MATE_SCORE / 2);
+
+        let with = game.search(4).unwrap();
+        assert_eq!(with.score, MATE_SCORE - 5);
+
+        let mut replay = game;
+        for mv in &with.pv {
+            replay.apply_move(*mv).unwrap();
+        }
+        assert!(replay.is_checkmate());
+    }
+
+    #[test]
+    pub fn promotions_are_ordered_before_killers() {
+        let game = Game::from_fen("4k3/1P6/8/8/8/8/8/RN2K3 w - -").unwrap();
+        let evaluator = ClassicalEvaluator::default();
+        let mut search = Search::new(SearchOptions::default(), &evaluator);
+
+        let quiet = game.parse_san("Nc3").unwrap();
+        search.killers.as_mut().unwrap().store(3, quiet);
+
+        let promotion = game.parse_san("b8=Q").unwrap();
+        assert!(
+            search.move_order_score(&game, promotion, 3, None)
+                > search.move_order_score(&game, quiet, 3, None)
+        );
+        assert!(search.move_order_score(&game, promotion, 3, None) > HistoryTable::MAX + 2);
+    }
+
+    #[test]
+    pub fn late_move_reductions_reduce_nodes() {
+        let game =
+            Game::from_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq -")
+                .unwrap();
+
+        let with_reductions = game.search(5).unwrap();
+        let without_reductions = game
+            .search_with_options(
+                5,
+                SearchOptions {
+                    late_move_reduction: None,
+                    ..SearchOptions::default()
+                },
+            )
+            .unwrap();
+
+        assert!(
+            with_reductions.nodes < without_reductions.nodes,
+            "{} >= {}",
+            with_reductions.nodes,
+            without_reductions.nodes
+        );
+        assert_eq!(with_reductions.best, without_reductions.best);
+    }
+
+    #[test]
+    pub fn constant_evaluator_still_plays_legal_moves() {
+        struct Constant;
+
+        impl Evaluator for Constant {
+            fn eval(&self, _: &Game) -> i32 {
+                42
+            }
+        }
+
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -",
+        ] {
+            let game = Game::from_fen(fen).unwrap();
+            let result = game
+                .search_with_evaluator(3, SearchOptions::default(), &Constant)
+                .unwrap();
+
+            assert!(
+                game.gen_all_moves().unwrap().contains(&result.best),
+                "{}",
+                fen
+            );
+            assert!(result.score.abs() <= 42, "{}", fen);
+        }
+
+        // Mates are still found, they don't depend on the evaluation
+        let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - -").unwrap();
+        let result = game
+            .search_with_evaluator(2, SearchOptions::default(), &Constant)
+            .unwrap();
+        assert_eq!(result.score, MATE_SCORE - 1);
+    }
+
+    #[test]
+    pub fn piece_square_tables_change_the_opening_move() {
+        let game = Game::start_pos();
+        let plain = ClassicalEvaluator {
+            tables: PieceSquareTables::zeros(),
+        };
+
+        // Reward knights in the center, c3 and f3 are on the way there
+        let mut tables = PieceSquareTables::zeros();
+        for y in 2..6 {
+            for x in 2..6 {
+                tables.midgame[PieceType::Knight as usize][y * 8 + x] = 40;
+            }
+        }
+        let knights = ClassicalEvaluator { tables };
+
+        let plain_move = game
+            .search_with_evaluator(3, SearchOptions::default(), &plain)
+            .unwrap()
+            .best;
+        let knight_move = game
+            .search_with_evaluator(3, SearchOptions::default(), &knights)
+            .unwrap()
+            .best;
+
+        assert_ne!(plain_move, knight_move);
+        assert!(
+            knight_move == game.parse_san("Nc3").unwrap()
+                || knight_move == game.parse_san("Nf3").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn search_without_moves() {
+        let checkmate = Game::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - -").unwrap();
+
+        assert_eq!(checkmate.search(3), None);
+        assert_eq!(checkmate.best_move(3), None);
+    }
+}
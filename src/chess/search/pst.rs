@@ -0,0 +1,215 @@
+use crate::{Bitboard, Board, Color, PieceType};
+
+/// A bonus in centipawns for each tile, seen from white with a8 first and h1 last
+pub type Table = [i32; 64];
+
+#[rustfmt::skip]
+const PAWN: Table = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    10, 10, 20, 30, 30, 20, 10, 10,
+     5,  5, 10, 25, 25, 10,  5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const PAWN_ENDGAME: Table = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    80, 80, 80, 80, 80, 80, 80, 80,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    30, 30, 30, 30, 30, 30, 30, 30,
+    20, 20, 20, 20, 20, 20, 20, 20,
+    10, 10, 10, 10, 10, 10, 10, 10,
+     0,  0,  0,  0,  0,  0,  0,  0,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT: Table = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP: Table = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK: Table = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN: Table = [
+    -20,-10,-10, -5, -5,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5,  5,  5,  5,  0,-10,
+     -5,  0,  5,  5,  5,  5,  0, -5,
+      0,  0,  5,  5,  5,  5,  0, -5,
+    -10,  5,  5,  5,  5,  5,  0,-10,
+    -10,  0,  5,  0,  0,  0,  0,-10,
+    -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const KING: Table = [
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -20,-30,-30,-40,-40,-30,-30,-20,
+    -10,-20,-20,-20,-20,-20,-20,-10,
+     20, 20,  0,  0,  0,  0, 20, 20,
+     20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+#[rustfmt::skip]
+const KING_ENDGAME: Table = [
+    -50,-40,-30,-20,-20,-30,-40,-50,
+    -30,-20,-10,  0,  0,-10,-20,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-30,  0,  0,  0,  0,-30,-30,
+    -50,-30,-30,-30,-30,-30,-30,-50,
+];
+
+/// Bonuses for where the pieces stand, used by [`ClassicalEvaluator`](crate::ClassicalEvaluator)
+///
+/// There is one table for each piece type, indexed in the order of [`PieceType::all`], for the
+/// middlegame and one for the endgame. The tables are written from the side of white, black uses
+/// them mirrored. The score is blended between the two with [`Game::phase`](crate::Game::phase),
+/// so the endgame tables take over as pieces are traded.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::{Board, Color, PieceSquareTables, PieceType};
+///
+/// let mut tables = PieceSquareTables::zeros();
+/// // A bonus for a knight on d4
+/// tables.midgame[PieceType::Knight as usize][4 * 8 + 3] = 50;
+///
+/// let board = Board::from_fen("4k3/8/8/8/3N4/8/8/4K3").unwrap();
+/// assert_eq!(tables.score(&board, Color::White, 24), 50);
+/// assert_eq!(tables.score(&board, Color::White, 0), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceSquareTables {
+    pub midgame: [Table; 6],
+    pub endgame: [Table; 6],
+}
+
+impl PieceSquareTables {
+    /// The tables used by default
+    pub const DEFAULT: PieceSquareTables = PieceSquareTables {
+        midgame: [PAWN, KNIGHT, BISHOP, ROOK, QUEEN, KING],
+        endgame: [PAWN_ENDGAME, KNIGHT, BISHOP, ROOK, QUEEN, KING_ENDGAME],
+    };
+
+    /// Returns tables without any bonuses
+    pub fn zeros() -> PieceSquareTables {
+        PieceSquareTables {
+            midgame: [[0; 64]; 6],
+            endgame: [[0; 64]; 6],
+        }
+    }
+
+    /// Returns the summed bonus of the pieces of a color
+    ///
+    /// # Arguments
+    /// * `board` - The board with the pieces
+    /// * `color` - The color to score
+    /// * `phase` - The phase of the game from 0 (endgame) to 24 (opening), see
+    ///   [`Game::phase`](crate::Game::phase)
+    pub fn score(&self, board: &Board, color: Color, phase: u8) -> i32 {
+        let phase = phase.min(24) as i32;
+        let mut midgame = 0;
+        let mut endgame = 0;
+
+        for piece_type in PieceType::all() {
+            for (x, y) in Bitboard(board.pieces(piece_type, color)) {
+                // The tables are written from the side of white with rank 8 first, so the rank is
+                // flipped for white and used as is for black
+                let index = match color {
+                    Color::White => (7 - y) * 8 + x,
+                    Color::Black => y * 8 + x,
+                };
+
+                midgame += self.midgame[piece_type as usize][index];
+                endgame += self.endgame[piece_type as usize][index];
+            }
+        }
+
+        (midgame * phase + endgame * (24 - phase)) / 24
+    }
+}
+
+impl Default for PieceSquareTables {
+    fn default() -> PieceSquareTables {
+        PieceSquareTables::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn tables_are_mirrored_for_black() {
+        let tables = PieceSquareTables::default();
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+
+        for phase in [0, 12, 24] {
+            assert_eq!(
+                tables.score(&board, Color::White, phase),
+                tables.score(&board, Color::Black, phase)
+            );
+        }
+
+        // A white pawn on e7 and a black pawn on e2 are both one step from promoting
+        let board = Board::from_fen("4k3/4P3/8/8/8/8/4p3/4K3").unwrap();
+        assert_eq!(
+            tables.score(&board, Color::White, 0),
+            tables.score(&board, Color::Black, 0)
+        );
+        assert!(tables.score(&board, Color::White, 0) > 0);
+    }
+
+    #[test]
+    pub fn the_phase_blends_the_tables() {
+        let mut tables = PieceSquareTables::zeros();
+        tables.midgame[PieceType::King as usize][60] = 48;
+        tables.endgame[PieceType::King as usize][60] = -48;
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3").unwrap();
+        assert_eq!(tables.score(&board, Color::White, 24), 48);
+        assert_eq!(tables.score(&board, Color::White, 12), 0);
+        assert_eq!(tables.score(&board, Color::White, 0), -48);
+        assert_eq!(tables.score(&board, Color::Black, 24), 48);
+    }
+}
@@ -0,0 +1,88 @@
+use alloc::{vec, vec::Vec};
+
+use crate::Move;
+
+use super::MATE_SCORE;
+
+/// The amount of entries in the table, a power of two so the key can be masked
+const ENTRIES: usize = 1 << 16;
+
+/// Scores this close to the mate score are mate scores that depend on the ply
+const MATE_THRESHOLD: i32 = MATE_SCORE - 1_000;
+
+/// How the stored score relates to the real score of the position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Bound {
+    /// The score is the real score
+    Exact,
+    /// The search failed high, the real score is at least the stored score
+    Lower,
+    /// The search failed low, the real score is at most the stored score
+    Upper,
+}
+
+/// A searched position
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Entry {
+    pub key: u64,
+    pub depth: u32,
+    pub score: i32,
+    pub bound: Bound,
+    pub best: Option<Move>,
+}
+
+/// Internal table of positions that have already been searched, keyed by their Polyglot key
+///
+/// The same position is often reached through different move orders, the table lets the search
+/// reuse the result instead of searching it again. Entries are always replaced.
+pub(super) struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+}
+
+impl TranspositionTable {
+    pub fn new() -> TranspositionTable {
+        TranspositionTable {
+            entries: vec![None; ENTRIES],
+        }
+    }
+
+    /// Returns the entry of a position, with mate scores relative to `ply`
+    pub fn probe(&self, key: u64, ply: u32) -> Option<Entry> {
+        let entry = self.entries[key as usize & (ENTRIES - 1)].filter(|e| e.key == key)?;
+
+        Some(Entry {
+            score: from_tt_score(entry.score, ply),
+            ..entry
+        })
+    }
+
+    /// Stores the result of searching a position, `entry.score` is relative to `ply`
+    pub fn store(&mut self, entry: Entry, ply: u32) {
+        self.entries[entry.key as usize & (ENTRIES - 1)] = Some(Entry {
+            score: to_tt_score(entry.score, ply),
+            ..entry
+        });
+    }
+}
+
+/// Internal helper that makes mate scores relative to the position instead of the root
+fn to_tt_score(score: i32, ply: u32) -> i32 {
+    if score > MATE_THRESHOLD {
+        score + ply as i32
+    } else if score < -MATE_THRESHOLD {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+/// Internal helper that makes mate scores relative to the root again
+fn from_tt_score(score: i32, ply: u32) -> i32 {
+    if score > MATE_THRESHOLD {
+        score - ply as i32
+    } else if score < -MATE_THRESHOLD {
+        score + ply as i32
+    } else {
+        score
+    }
+}
@@ -0,0 +1,159 @@
+//! `wasm-bindgen` bindings around [`Game`] for WebAssembly front-ends
+//!
+//! Only strings and vectors of strings cross the boundary, FENs and UCI moves are used as the
+//! formats on the JavaScript side. When the crate is built into a `wasm32` binary the
+//! `WasmGame` class is exported to JavaScript, on other targets the wrapper is plain Rust and can
+//! be used and tested like any other type.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{Color, Game, GameLine};
+
+/// A game that is driven with FEN and UCI strings
+///
+/// The moves played through [`WasmGame::apply_move`] are kept, so the game can end by threefold
+/// repetition.
+///
+/// # Examples
+/// ```
+/// use fritiofr_chess::wasm::WasmGame;
+///
+/// let mut game = WasmGame::new();
+/// game.apply_move("e2e4").unwrap();
+///
+/// assert_eq!(game.legal_moves().len(), 20);
+/// assert_eq!(game.result(), "*");
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct WasmGame {
+    line: GameLine,
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    /// Creates a game at the starting position
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGame {
+        WasmGame::default()
+    }
+
+    /// Creates a game from a FEN string
+    ///
+    /// # Returns
+    /// * `Result<WasmGame, String>` - The game or a description of why the FEN is invalid
+    #[wasm_bindgen(js_name = fromFen)]
+    pub fn from_fen(fen: &str) -> Result<WasmGame, String> {
+        Game::from_fen(fen)
+            .map(|game| WasmGame {
+                line: GameLine::new(game),
+            })
+            .map_err(|err| format!("Invalid FEN: {:?}", err))
+    }
+
+    /// Returns the position as a FEN string
+    pub fn fen(&self) -> String {
+        self.line.game().fen()
+    }
+
+    /// Returns all legal moves in the UCI format, e.g `e2e4`
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String> {
+        self.line
+            .game()
+            .legal_moves_iter()
+            .map(|m| m.to_uci())
+            .collect()
+    }
+
+    /// Applies a move in the UCI format
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - Nothing or a description of why the move couldn't be played
+    #[wasm_bindgen(js_name = applyMove)]
+    pub fn apply_move(&mut self, uci: &str) -> Result<(), String> {
+        let mv = self
+            .line
+            .game()
+            .parse_uci(uci)
+            .map_err(|err| format!("Invalid move {}: {:?}", uci, err))?;
+
+        self.line
+            .apply_move(mv)
+            .map_err(|err| format!("Invalid move {}: {:?}", uci, err))
+    }
+
+    /// Returns the result of the game in the PGN format
+    ///
+    /// # Returns
+    /// * `String` - `1-0` or `0-1` after a checkmate, `1/2-1/2` when the game is drawn and `*`
+    ///   while the game is still going
+    pub fn result(&self) -> String {
+        let game = self.line.game();
+
+        let result = if game.is_checkmate() {
+            match game.turn() {
+                Color::White => "0-1",
+                Color::Black => "1-0",
+            }
+        } else if self.line.is_draw() {
+            "1/2-1/2"
+        } else {
+            "*"
+        };
+
+        result.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn play_a_game_through_the_wrapper() {
+        let mut game = WasmGame::new();
+        assert_eq!(game.legal_moves().len(), 20);
+
+        // Fool's mate
+        for uci in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            assert_eq!(game.result(), "*");
+            game.apply_move(uci).unwrap();
+        }
+
+        assert_eq!(game.result(), "0-1");
+        assert!(game.legal_moves().is_empty());
+        assert_eq!(
+            game.fen(),
+            "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq -"
+        );
+
+        assert!(game.apply_move("e2e4").is_err());
+        assert!(WasmGame::from_fen("8/8 w - -").is_err());
+        assert_eq!(
+            WasmGame::from_fen("k7/8/1Q6/8/8/8/8/7K b - -")
+                .unwrap()
+                .result(),
+            "1/2-1/2"
+        );
+    }
+
+    #[test]
+    pub fn threefold_repetition_through_the_wrapper() {
+        let mut game = WasmGame::new();
+
+        for uci in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1"] {
+            game.apply_move(uci).unwrap();
+            assert_eq!(game.result(), "*");
+        }
+
+        game.apply_move("f6g8").unwrap();
+        assert_eq!(game.result(), "1/2-1/2");
+    }
+}
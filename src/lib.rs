@@ -15,10 +15,27 @@
 //! The idea on how to play a game of chess with this library:
 //! - Start by checking `is_checkmate` and `is_stalemate` to see if the game has ended
 //! - Call either `gen_moves` or `gen_all_moves` to get a vector containing all the moves for the
-//! current turn
+//!   current turn
 //! - Pick a move from the vector and apply it to the game with `apply_move`
 //! - Repeat 🔁
 //!
+//! ## Coordinates 🧭
+//!
+//! Tiles are written as `(x, y)` where x goes from the a-file to the h-file and y goes from rank 1
+//! to rank 8, so `(0, 0)` is a1 and `(4, 7)` is e8. Tile `(x, y)` is bit `y * 8 + x` of a
+//! bitboard. Use the `coord` module to convert between coordinates and square names like `e4`
+//! instead of doing the math yourself.
+//!
+//! ## Features 🧩
+//!
+//! - `std` (default): Enables PGN and EPD parsing, Polyglot and opening books, and the `Display`
+//!   and `Error` impls of the errors. Without it the crate is `no_std` and only needs an
+//!   allocator, the board, move generation, FEN and search still work.
+//! - `wasm-wrapper`: Adds the `wasm` module, a `wasm-bindgen` wrapper around `Game` that only
+//!   uses strings, for WebAssembly front-ends.
+//! - `parallel`: Adds `Game::perft_parallel`, which splits perft over threads with `rayon`. Needs
+//!   `std`.
+//!
 //! ## Things that are not implemented by design 🚫
 //!
 //! - There is no real way to switch turns in the game.
@@ -33,6 +50,10 @@
 //! *Psst i check my mail more often than my discord, so if you want to reach me quickly send a
 //! mail*
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod chess;
 pub use crate::chess::*;
 
@@ -41,26 +62,11 @@ pub use crate::chess::*;
 mod tests {
     use super::*;
 
-    /// Function that searches game recursively for moves
-    /// Used for perft testing
-    fn amount_of_moves_recursively(game: Game, depth: u8) -> u64 {
-        if depth == 0 {
-            return 1;
-        }
-        let mut amount = 0;
-        for m in game.gen_all_moves().unwrap_or(Vec::new()) {
-            let mut game = game.clone();
-            game.apply_move(m).unwrap();
-            amount += amount_of_moves_recursively(game, depth - 1);
-        }
-        amount
-    }
-
     #[test]
     #[ignore]
     fn perft_1() {
         let game = Game::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - -").unwrap();
-        let amount_of_moves = amount_of_moves_recursively(game, 3);
+        let amount_of_moves = game.perft(3);
         assert_eq!(amount_of_moves, 2812);
     }
 
@@ -69,7 +75,7 @@ mod tests {
     fn perft_2() {
         let game =
             Game::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq -").unwrap();
-        let amount_of_moves = amount_of_moves_recursively(game, 3);
+        let amount_of_moves = game.perft(3);
         assert_eq!(amount_of_moves, 9467);
     }
 
@@ -79,7 +85,7 @@ mod tests {
         let game =
             Game::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -")
                 .unwrap();
-        let amount_of_moves = amount_of_moves_recursively(game, 3);
+        let amount_of_moves = game.perft(3);
         assert_eq!(amount_of_moves, 97862);
     }
 
@@ -87,7 +93,7 @@ mod tests {
     #[ignore]
     fn perft_4() {
         let game = Game::from_fen("r3k2r/8/3Q4/8/8/5q2/8/R3K2R b KQkq -").unwrap();
-        let amount_of_moves = amount_of_moves_recursively(game, 4);
+        let amount_of_moves = game.perft(4);
         assert_eq!(amount_of_moves, 1720476);
     }
 
@@ -95,7 +101,7 @@ mod tests {
     #[ignore]
     fn perft_5() {
         let game = Game::from_fen("8/8/1P2K3/8/2n5/1q6/8/5k2 b - -").unwrap();
-        let amount_of_moves = amount_of_moves_recursively(game, 5);
+        let amount_of_moves = game.perft(5);
         assert_eq!(amount_of_moves, 1004658);
     }
 
@@ -103,7 +109,7 @@ mod tests {
     #[ignore]
     fn perft_6() {
         let game = Game::from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ -").unwrap();
-        let amount_of_moves = amount_of_moves_recursively(game, 3);
+        let amount_of_moves = game.perft(3);
         assert_eq!(amount_of_moves, 62379);
     }
 }
@@ -0,0 +1,63 @@
+//! Perft of the standard test positions from the chessprogramming wiki
+//!
+//! The depths are kept low enough to run in debug builds, the deeper counts are in the ignored
+//! tests of the crate.
+
+mod testutil;
+
+use testutil::assert_perft_matches;
+
+#[test]
+fn start_position() {
+    assert_perft_matches(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &[20, 400, 8902, 197281],
+    );
+}
+
+/// Castling in every form, en passant and promotions
+#[test]
+fn kiwipete() {
+    assert_perft_matches(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2039, 97862],
+    );
+}
+
+/// En passant that exposes the king along the rank
+#[test]
+fn position_3() {
+    assert_perft_matches(
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        &[14, 191, 2812, 43238],
+    );
+}
+
+/// Promotions with capture and castling out of check, from both sides
+#[test]
+fn position_4() {
+    assert_perft_matches(
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        &[6, 264, 9467],
+    );
+    assert_perft_matches(
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        &[6, 264, 9467],
+    );
+}
+
+#[test]
+fn position_5() {
+    assert_perft_matches(
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        &[44, 1486, 62379],
+    );
+}
+
+#[test]
+fn position_6() {
+    assert_perft_matches(
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        &[46, 2079, 89890],
+    );
+}
@@ -0,0 +1,19 @@
+use fritiofr_chess::Game;
+
+/// Runs perft from a position for every depth up to the length of `expected`
+///
+/// # Arguments
+/// * `fen` - The position to start from
+/// * `expected` - The amount of leaf nodes at depth 1, 2 and so on
+///
+/// # Panics
+/// If the FEN string is invalid or any depth has the wrong amount of nodes
+pub fn assert_perft_matches(fen: &str, expected: &[u64]) {
+    let game = Game::from_fen(fen).unwrap();
+
+    for (depth, nodes) in expected.iter().enumerate() {
+        let depth = depth as u8 + 1;
+
+        assert_eq!(game.perft(depth), *nodes, "perft({}) of {}", depth, fen);
+    }
+}